@@ -7,8 +7,9 @@ The main interface to MWA data.
  */
 use chrono::{DateTime, Duration, FixedOffset};
 use fitsio::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::ops::Range;
 use std::path::*;
 
 use crate::antenna::*;
@@ -17,6 +18,7 @@ use crate::convert::*;
 use crate::fits_read::*;
 use crate::gpubox::*;
 use crate::misc::*;
+use crate::passband::{self, PassbandTable};
 use crate::rfinput::*;
 use crate::timestep::*;
 use crate::*;
@@ -213,6 +215,50 @@ pub struct mwalibContext {
     pub num_gpubox_files: usize,
     /// A conversion table to optimise reading of legacy MWA HDUs
     pub legacy_conversion_table: Vec<mwalibLegacyConversionBaseline>,
+
+    /// Whether `read_by_baseline`/`read_by_frequency` apply the differential
+    /// cable-length phase correction. See `set_visibility_corrections`.
+    pub cable_length_correction_enabled: bool,
+    /// Whether `read_by_baseline`/`read_by_frequency` apply the geometric
+    /// (phase-tracking) correction. See `set_visibility_corrections`.
+    pub geometric_correction_enabled: bool,
+    /// Whether `read_by_baseline`/`read_by_frequency` apply the differential
+    /// digital-gain correction. See `set_visibility_corrections`.
+    pub digital_gain_correction_enabled: bool,
+    /// Whether `read_by_baseline`/`read_by_frequency` apply the PFB passband
+    /// gain correction. See `set_visibility_corrections`.
+    pub passband_correction_enabled: bool,
+    /// Which corrections `correct_visibilities`/`correct_visibilities_by_frequency`
+    /// have already applied, keyed by `(timestep_index, coarse_channel_index)`,
+    /// so a correction already baked into a buffer is never applied twice.
+    pub applied_corrections: HashMap<(usize, usize), CorrectionFlags>,
+
+    /// The narrowest contiguous (coarse channel range, fine channel range)
+    /// with no flagged edges, as last computed by
+    /// `compute_smallest_unflagged_channel_band`. `write_uvfits` and
+    /// `read_by_baseline_averaged` callers should prefer this over the full
+    /// observation bandwidth, once it has been computed.
+    pub default_channel_band: Option<(Range<usize>, Range<usize>)>,
+
+    /// Which antenna-pair convention `read_by_baseline`/`read_by_frequency`
+    /// emit each baseline's visibilities in. See `set_baseline_conjugation`.
+    pub baseline_conjugation: BaselineConjugation,
+}
+
+/// Which of a baseline's two antennas is treated as the "first" antenna when
+/// emitting its visibility. Raw gpubox data is always `Ant1Ant2`;
+/// `read_by_baseline`/`read_by_frequency` emit whichever convention
+/// `set_baseline_conjugation` selects. Some downstream calibration/imaging code assumes the
+/// opposite convention; requesting `Ant2Ant1` conjugates each sample in
+/// place rather than requiring a separate post-read transpose.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BaselineConjugation {
+    /// The convention produced natively by the correlator: baseline `b`'s
+    /// visibility is `V(ant1, ant2)`.
+    Ant1Ant2,
+    /// The conjugate convention: baseline `b`'s visibility is `V(ant2,
+    /// ant1) = conj(V(ant1, ant2))`.
+    Ant2Ant1,
 }
 
 impl mwalibContext {
@@ -247,15 +293,16 @@ impl mwalibContext {
 
         // Pull out observation details. Save the metafits HDU for faster
         // accesses.
-        let mut metafits_fptr =
-            FitsFile::open(&metafits).with_context(|| format!("Failed to open {:?}", metafits))?;
+        let metafits_path: PathBuf = AsRef::<Path>::as_ref(metafits).to_path_buf();
+        let mut metafits_fptr = FitsFile::open(&metafits)
+            .map_err(|e| ErrorKind::fits_open(metafits_path.clone(), e))?;
         let metafits_hdu = metafits_fptr
             .hdu(0)
-            .with_context(|| format!("Failed to open HDU 1 (primary hdu) for {:?}", metafits))?;
+            .map_err(|e| ErrorKind::fits_hdu(metafits_path.clone(), 0, e))?;
 
         let metafits_tile_table_hdu = metafits_fptr
             .hdu(1)
-            .with_context(|| format!("Failed to open HDU 2 (tiledata table) for {:?}", metafits))?;
+            .map_err(|e| ErrorKind::fits_hdu(metafits_path.clone(), 1, e))?;
 
         // Populate obsid from the metafits
         let obsid = get_required_fits_key(&mut metafits_fptr, &metafits_hdu, "GPSTIME")
@@ -557,6 +604,13 @@ impl mwalibContext {
             num_timestep_coarse_channel_bytes: hdu_size * 4,
             num_timestep_coarse_channel_floats: hdu_size,
             legacy_conversion_table,
+            cable_length_correction_enabled: false,
+            geometric_correction_enabled: false,
+            digital_gain_correction_enabled: false,
+            passband_correction_enabled: false,
+            applied_corrections: HashMap::new(),
+            default_channel_band: None,
+            baseline_conjugation: BaselineConjugation::Ant1Ant2,
         })
     }
 
@@ -677,6 +731,53 @@ impl mwalibContext {
         Ok(())
     }
 
+    /// Whether a gpubox HDU backs the given (timestep, coarse channel) pair,
+    /// i.e. whether it has an entry in `gpubox_time_map`. Observations missing
+    /// some gpubox files will have gaps here.
+    fn gpubox_hdu_is_present(&self, timestep_index: usize, coarse_channel_index: usize) -> bool {
+        let coarse_channel = self.coarse_channels[coarse_channel_index].gpubox_number;
+        self.gpubox_time_map
+            .get(&self.timesteps[timestep_index].unix_time_ms)
+            .map_or(false, |by_chan| by_chan.contains_key(&coarse_channel))
+    }
+
+    /// Read a single timestep for a single coarse channel, along with a
+    /// per-sample weight, gap-filling with zeros instead of erroring if the
+    /// underlying gpubox HDU is missing from `gpubox_time_map`.
+    /// The output visibilities are in order:
+    /// [baseline][frequency][pol][r][i]
+    ///
+    /// # Arguments
+    ///
+    /// * `timestep_index` - index within the timestep array for the desired timestep. This corresponds
+    ///                      to the element within mwalibContext.timesteps.
+    ///
+    /// * `coarse_channel_index` - index within the coarse_channel array for the desired coarse channel. This corresponds
+    ///                      to the element within mwalibContext.coarse_channels.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing a tuple of (visibilities in [baseline][frequency][pol][r][i] order,
+    ///   weights in [baseline][frequency][pol] order; 1.0 where the HDU was present, 0.0 where it
+    ///   was gap-filled), if Ok.
+    ///
+    ///
+    pub fn read_by_baseline_with_weights(
+        &mut self,
+        timestep_index: usize,
+        coarse_channel_index: usize,
+    ) -> Result<(Vec<f32>, Vec<f32>), fitsio::errors::Error> {
+        let num_samples = self.num_baselines * self.num_fine_channels_per_coarse * self.num_visibility_pols;
+
+        if self.gpubox_hdu_is_present(timestep_index, coarse_channel_index) {
+            let data = self.read_by_baseline(timestep_index, coarse_channel_index)?;
+            Ok((data, vec![1.; num_samples]))
+        } else {
+            Ok((vec![0.; num_samples * 2], vec![0.; num_samples]))
+        }
+    }
+
     /// Read a single timestep for a single coarse channel
     /// The output visibilities are in order:
     /// [baseline][frequency][pol][r][i]
@@ -728,7 +829,7 @@ impl mwalibContext {
         let hdu = fptr.hdu(hdu_index)?;
         output_buffer = hdu.read_image(&mut fptr)?;
         // If legacy correlator, then convert the HDU into the correct output format
-        if self.corr_version == CorrelatorVersion::OldLegacy
+        let mut corrected_buffer = if self.corr_version == CorrelatorVersion::OldLegacy
             || self.corr_version == CorrelatorVersion::Legacy
         {
             convert::convert_legacy_hdu_to_mwax_baseline_order(
@@ -738,9 +839,63 @@ impl mwalibContext {
                 self.num_fine_channels_per_coarse,
             );
 
-            Ok(temp_buffer)
+            temp_buffer
+        } else {
+            output_buffer
+        };
+
+        self.correct_visibilities_inner(
+            &mut corrected_buffer,
+            timestep_index,
+            coarse_channel_index,
+            CorrectionFlags {
+                cable_length: self.cable_length_correction_enabled,
+                geometric: self.geometric_correction_enabled,
+                digital_gain: self.digital_gain_correction_enabled,
+                passband: self.passband_correction_enabled,
+            },
+            true,
+        );
+
+        self.conjugate_visibilities(&mut corrected_buffer);
+
+        Ok(corrected_buffer)
+    }
+
+    /// Read a single timestep for a single coarse channel, along with a
+    /// per-sample weight, gap-filling with zeros instead of erroring if the
+    /// underlying gpubox HDU is missing from `gpubox_time_map`.
+    /// The output visibilities are in order:
+    /// [frequency][baseline][pol][r][i]
+    ///
+    /// # Arguments
+    ///
+    /// * `timestep_index` - index within the timestep array for the desired timestep. This corresponds
+    ///                      to the element within mwalibContext.timesteps.
+    ///
+    /// * `coarse_channel_index` - index within the coarse_channel array for the desired coarse channel. This corresponds
+    ///                      to the element within mwalibContext.coarse_channels.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing a tuple of (visibilities in [frequency][baseline][pol][r][i] order,
+    ///   weights in [frequency][baseline][pol] order; 1.0 where the HDU was present, 0.0 where it
+    ///   was gap-filled), if Ok.
+    ///
+    ///
+    pub fn read_by_frequency_with_weights(
+        &mut self,
+        timestep_index: usize,
+        coarse_channel_index: usize,
+    ) -> Result<(Vec<f32>, Vec<f32>), fitsio::errors::Error> {
+        let num_samples = self.num_baselines * self.num_fine_channels_per_coarse * self.num_visibility_pols;
+
+        if self.gpubox_hdu_is_present(timestep_index, coarse_channel_index) {
+            let data = self.read_by_frequency(timestep_index, coarse_channel_index)?;
+            Ok((data, vec![1.; num_samples]))
         } else {
-            Ok(output_buffer)
+            Ok((vec![0.; num_samples * 2], vec![0.; num_samples]))
         }
     }
 
@@ -798,8 +953,6 @@ impl mwalibContext {
                 &mut temp_buffer,
                 self.num_fine_channels_per_coarse,
             );
-
-            Ok(temp_buffer)
         } else {
             // Do conversion for mwax (it is in baseline order, we want it in freq order)
             convert::convert_mwax_hdu_to_frequency_order(
@@ -809,178 +962,1571 @@ impl mwalibContext {
                 self.num_fine_channels_per_coarse,
                 self.num_visibility_pols,
             );
-
-            Ok(temp_buffer)
         }
+
+        self.correct_visibilities_inner(
+            &mut temp_buffer,
+            timestep_index,
+            coarse_channel_index,
+            CorrectionFlags {
+                cable_length: self.cable_length_correction_enabled,
+                geometric: self.geometric_correction_enabled,
+                digital_gain: self.digital_gain_correction_enabled,
+                passband: self.passband_correction_enabled,
+            },
+            false,
+        );
+
+        self.conjugate_visibilities(&mut temp_buffer);
+
+        Ok(temp_buffer)
     }
 }
 
-/// Implements fmt::Display for mwalibContext struct
-///
-/// # Arguments
-///
-/// * `f` - A fmt::Formatter
-///
-///
-/// # Returns
-///
-/// * `fmt::Result` - Result of this method
-///
-///
-#[cfg_attr(tarpaulin, skip)]
-impl fmt::Display for mwalibContext {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // `size` is the number of floats (self.gpubox_hdu_size) multiplied by 4
-        // bytes per float, divided by 1024^2 to get MiB.
-        let size = (self.num_timestep_coarse_channel_floats * 4) as f64 / (1024 * 1024) as f64;
-        writeln!(
-            f,
-            r#"mwalibContext (
-    Correlator version:       {},
-
-    MWA latitude:             {} degrees,
-    MWA longitude:            {} degrees
-    MWA altitude:             {} m,
+/// Mean sidereal seconds per solar (UNIX) second, used to advance the LST
+/// recorded at the start of the observation to the LST of a later timestep.
+const SIDEREAL_SECONDS_PER_SOLAR_SECOND: f64 = 1.002_737_909_350_795;
 
-    obsid:                    {},
+/// Speed of light, in metres per second.
+const SPEED_OF_LIGHT_M_PER_S: f64 = 299_792_458.0;
 
-    Creator:                  {},
-    Project ID:               {},
-    Observation Name:         {},
-    Receivers:                {:?},    
-    Delays:                   {:?},
-    Global attenuation:       {} dB,
+/// `(ant1_pol_is_x, ant2_pol_is_x)` for each of the four visibility pols, in
+/// the standard XX,XY,YX,YY order.
+const VISIBILITY_POL_ORDER: [(bool, bool); 4] =
+    [(true, true), (true, false), (false, true), (false, false)];
 
-    Scheduled start (UNIX)    {},
-    Scheduled end (UNIX)      {}, 
-    Scheduled start (GPS)     {},
-    Scheduled end (GPS)       {}, 
-    Scheduled start (utc)     {},
-    Scheduled end (utc)       {},
-    Scheduled start (MJD)     {},
-    Scheduled end (MJD)       {},
-    Scheduled duration        {} s,                
-    Actual UNIX start time:   {},
-    Actual UNIX end time:     {},
-    Actual duration:          {} s,
-    Quack time:               {} s,
-    Good UNIX start time:     {},
+impl mwalibContext {
+    /// The local East-North-Height position of each tile (rf_input pair),
+    /// rotated into local XYZ (relative to the array centre) via
+    /// `enh_to_local_xyz`. X and Y pols of the same tile share a position; the
+    /// position is taken from whichever pol appears first in `rf_inputs`.
+    ///
+    /// # Returns
+    ///
+    /// * A `Vec` of local `(X, Y, Z)` coordinates in metres, one per entry of `self.antennas`.
+    ///
+    pub fn antenna_local_xyz_m(&self) -> Vec<(f64, f64, f64)> {
+        let mut antenna_xyz = vec![(0., 0., 0.); self.num_antennas];
+
+        for rf_input in &self.rf_inputs {
+            antenna_xyz[rf_input.antenna as usize] = enh_to_local_xyz(
+                rf_input.north_m,
+                rf_input.east_m,
+                rf_input.height_m,
+                self.mwa_latitude_radians,
+            );
+        }
 
-    R.A. (tile_pointing):     {} degrees,
-    Dec. (tile_pointing):     {} degrees,
-    R.A. (phase center):      {:?} degrees,
-    Dec. (phase center):      {:?} degrees,
-    Azimuth:                  {} degrees,
-    Altitude:                 {} degrees,
-    Sun altitude:             {} degrees,
-    Sun distance:             {} degrees,
-    Moon distance:            {} degrees,
-    Jupiter distance:         {} degrees,
-    LST:                      {} degrees,
-    Hour angle:               {} degrees,
-    Grid name:                {},
-    Grid number:              {},    
-    
-    num timesteps:            {},
-    timesteps:                {:?},
+        antenna_xyz
+    }
 
-    num antennas:             {},
-    antennas:                 {:?},
-    rf_inputs:                {:?},
+    /// Compute per-baseline UVW coordinates (in metres) for a single
+    /// timestep, phased to the observation's phase centre
+    /// (`ra_phase_center_degrees`/`dec_phase_center_degrees`).
+    ///
+    /// The LST recorded in the metafits (`lst_degrees`) only applies to the
+    /// start of the observation; to phase a later timestep correctly, the LST
+    /// is advanced by the elapsed time (converted from solar to sidereal
+    /// seconds) between `start_unix_time_milliseconds` and the timestep's own
+    /// `unix_time_ms`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestep_index` - index within the timestep array for the desired timestep. This corresponds
+    ///                      to the element within mwalibContext.timesteps.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing a `Vec` of `(u, v, w)` coordinates in metres, one per baseline (in the same order as `get_baseline_from_antennas`/`get_antennas_from_baseline`), if Ok.
+    ///
+    ///
+    pub fn uvw_for_timestep(&self, timestep_index: usize) -> Result<Vec<(f64, f64, f64)>, ErrorKind> {
+        let ra_phase_center_degrees = self.ra_phase_center_degrees.ok_or_else(|| {
+            ErrorKind::Custom(
+                "uvw_for_timestep: observation has no phase centre (RAPHASE/DECPHASE not set)"
+                    .to_string(),
+            )
+        })?;
+        let dec_phase_center_degrees = self.dec_phase_center_degrees.ok_or_else(|| {
+            ErrorKind::Custom(
+                "uvw_for_timestep: observation has no phase centre (RAPHASE/DECPHASE not set)"
+                    .to_string(),
+            )
+        })?;
+
+        let timestep = self.timesteps.get(timestep_index).ok_or_else(|| {
+            ErrorKind::Custom(format!(
+                "uvw_for_timestep: timestep index {} out of range (0..{})",
+                timestep_index, self.num_timesteps
+            ))
+        })?;
+
+        let elapsed_solar_seconds =
+            (timestep.unix_time_ms as f64 - self.start_unix_time_milliseconds as f64) / 1000.;
+        let elapsed_sidereal_radians = elapsed_solar_seconds * SIDEREAL_SECONDS_PER_SOLAR_SECOND
+            / 86400.
+            * 2.
+            * std::f64::consts::PI;
+        let lst_rad = self.lst_degrees.to_radians() + elapsed_sidereal_radians;
+
+        let hour_angle_rad = lst_rad - ra_phase_center_degrees.to_radians();
+        let dec_rad = dec_phase_center_degrees.to_radians();
+
+        let antenna_local_xyz = self.antenna_local_xyz_m();
+
+        Ok((0..self.num_baselines)
+            .map(|baseline| {
+                let (ant1, ant2) = get_antennas_from_baseline(
+                    BaselineOrder::CrossAndAuto,
+                    baseline,
+                    self.num_antennas,
+                )
+                .expect("baseline index within 0..num_baselines is always valid");
+
+                let (x1, y1, z1) = antenna_local_xyz[ant1];
+                let (x2, y2, z2) = antenna_local_xyz[ant2];
+
+                xyz_to_uvw(x2 - x1, y2 - y1, z2 - z1, hour_angle_rad, dec_rad)
+            })
+            .collect())
+    }
 
-    num baselines:            {},
-    num auto-correlations:    {},
-    num cross-correlations:   {},
+    /// The electrical length of each tile's X and Y dipole, as `(x_length_m,
+    /// y_length_m)`, indexed the same way as `self.antennas`.
+    fn antenna_pol_electrical_lengths_m(&self) -> Vec<(f64, f64)> {
+        let mut lengths = vec![(0., 0.); self.num_antennas];
+
+        for rf_input in &self.rf_inputs {
+            let antenna = rf_input.antenna as usize;
+            if rf_input.pol == "X" {
+                lengths[antenna].0 = rf_input.electrical_length_m;
+            } else {
+                lengths[antenna].1 = rf_input.electrical_length_m;
+            }
+        }
 
-    num antenna pols:         {},
-    num visibility pols:      {},
+        lengths
+    }
 
-    observation bandwidth:    {} MHz,
-    num coarse channels,      {},
-    coarse channels:          {:?},
+    /// The digital (PFB/receiver) gain of each tile's X and Y dipole for a
+    /// single coarse channel, as `(x_gain, y_gain)`, indexed the same way as
+    /// `self.antennas`. Tiles with no recorded gain for this coarse channel
+    /// default to unity.
+    fn antenna_pol_digital_gains(&self, coarse_channel_index: usize) -> Vec<(f64, f64)> {
+        let mut gains = vec![(1., 1.); self.num_antennas];
+
+        for rf_input in &self.rf_inputs {
+            let antenna = rf_input.antenna as usize;
+            let gain = rf_input
+                .digital_gains
+                .get(coarse_channel_index)
+                .copied()
+                .unwrap_or(1.);
+            if rf_input.pol == "X" {
+                gains[antenna].0 = gain;
+            } else {
+                gains[antenna].1 = gain;
+            }
+        }
 
-    Correlator Mode:
-    Mode:                     {},
-    fine channel resolution:  {} kHz,
-    integration time:         {:.2} s
-    num fine channels/coarse: {},
+        gains
+    }
 
-    gpubox HDU size:          {} MiB,
-    Memory usage per scan:    {} MiB,
+    /// Enable or disable the opt-in cable-length, geometric (phase-tracking)
+    /// and/or digital-gain corrections automatically applied to visibilities
+    /// by `read_by_baseline`/`read_by_frequency`. Equivalent to calling
+    /// `correct_visibilities`/`correct_visibilities_by_frequency` with the
+    /// same flags on every subsequent read.
+    ///
+    /// # Arguments
+    ///
+    /// * `cable_length_correction` - if true, correct for the differential cable length between each baseline's two rf_inputs.
+    ///
+    /// * `geometric_correction` - if true, correct for the geometric (w-term) delay to the phase centre.
+    ///
+    /// * `digital_gain_correction` - if true, correct for the differential digital gain between each baseline's two rf_inputs.
+    ///
+    /// * `passband_correction` - if true, correct for the MWA PFB's coarse-channel passband gain shape. See `correct_passband`.
+    ///
+    pub fn set_visibility_corrections(
+        &mut self,
+        cable_length_correction: bool,
+        geometric_correction: bool,
+        digital_gain_correction: bool,
+        passband_correction: bool,
+    ) {
+        self.cable_length_correction_enabled = cable_length_correction;
+        self.geometric_correction_enabled = geometric_correction;
+        self.digital_gain_correction_enabled = digital_gain_correction;
+        self.passband_correction_enabled = passband_correction;
+    }
 
-    metafits filename:        {},
-    gpubox batches:           {:#?},
-)"#,
-            self.corr_version,
-            self.mwa_latitude_radians.to_degrees(),
-            self.mwa_longitude_radians.to_degrees(),
-            self.mwa_altitude_metres,
-            self.obsid,
-            self.creator,
-            self.project_id,
-            self.observation_name,
-            self.receivers,
-            self.delays,
-            self.global_analogue_attenuation_db,
-            self.scheduled_start_unix_time_milliseconds as f64 / 1e3,
-            self.scheduled_end_unix_time_milliseconds as f64 / 1e3,
-            self.scheduled_start_gpstime_milliseconds as f64 / 1e3,
-            self.scheduled_end_gpstime_milliseconds as f64 / 1e3,
-            self.scheduled_start_utc,
-            self.scheduled_end_utc,
-            self.scheduled_start_mjd,
-            self.scheduled_end_mjd,
-            self.scheduled_duration_milliseconds as f64 / 1e3,
-            self.start_unix_time_milliseconds as f64 / 1e3,
-            self.end_unix_time_milliseconds as f64 / 1e3,
-            self.duration_milliseconds as f64 / 1e3,
-            self.quack_time_duration_milliseconds as f64 / 1e3,
-            self.good_time_unix_milliseconds as f64 / 1e3,
-            self.ra_tile_pointing_degrees,
-            self.dec_tile_pointing_degrees,
-            self.ra_phase_center_degrees,
-            self.dec_phase_center_degrees,
-            self.azimuth_degrees,
-            self.altitude_degrees,
-            self.sun_altitude_degrees,
-            self.sun_distance_degrees,
-            self.moon_distance_degrees,
-            self.jupiter_distance_degrees,
-            self.lst_degrees,
-            self.hour_angle_string,
-            self.grid_name,
-            self.grid_number,
-            self.num_timesteps,
-            self.timesteps,
-            self.num_antennas,
-            self.antennas,
-            self.rf_inputs,
-            self.num_baselines,
-            self.num_antennas,
-            self.num_baselines - self.num_antennas,
-            self.num_antenna_pols,
-            self.num_visibility_pols,
-            self.observation_bandwidth_hz as f64 / 1e6,
-            self.num_coarse_channels,
-            self.coarse_channels,
-            self.mode,
-            self.fine_channel_width_hz as f64 / 1e3,
-            self.integration_time_milliseconds as f64 / 1e3,
-            self.num_fine_channels_per_coarse,
-            size,
-            size * self.num_gpubox_files as f64,
-            self.metafits_filename,
-            self.gpubox_batches,
-        )
+    /// Select which antenna-pair convention `read_by_baseline`/
+    /// `read_by_frequency` emit each baseline's visibilities in. Defaults to
+    /// `BaselineConjugation::Ant1Ant2`, the convention the correlator
+    /// natively produces.
+    pub fn set_baseline_conjugation(&mut self, conjugation: BaselineConjugation) {
+        self.baseline_conjugation = conjugation;
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use float_cmp::*;
+    /// Conjugate every complex sample in a buffer of interleaved `[r][i]`
+    /// pairs, in place. Used to switch a read buffer from the native
+    /// `Ant1Ant2` convention to `Ant2Ant1` (`V(ant2, ant1) = conj(V(ant1,
+    /// ant2))`); the antenna pair relabelling itself is a no-op, since each
+    /// baseline already occupies a fixed slot in the buffer regardless of
+    /// which antenna is considered "first".
+    fn conjugate_visibilities(&self, buffer: &mut [f32]) {
+        if self.baseline_conjugation == BaselineConjugation::Ant2Ant1 {
+            for imag in buffer.iter_mut().skip(1).step_by(2) {
+                *imag = -*imag;
+            }
+        }
+    }
 
-    #[test]
+    /// Apply the cable-length, geometric and/or digital-gain corrections
+    /// selected by `flags` to a buffer in the layout produced by
+    /// `read_by_baseline` (`[baseline][frequency][pol][r][i]`), in-place.
+    ///
+    /// Corrections already recorded as applied to this `(timestep_index,
+    /// coarse_channel_index)` (by an earlier call to this method, or to
+    /// `correct_visibilities_by_frequency`) are skipped, so calling this more
+    /// than once for the same buffer never double-corrects it. If the
+    /// geometric correction is requested but the observation has no phase
+    /// centre (so UVW cannot be computed), the geometric term is silently
+    /// skipped for this call.
+    pub fn correct_visibilities(
+        &mut self,
+        buffer: &mut [f32],
+        timestep_index: usize,
+        coarse_channel_index: usize,
+        flags: CorrectionFlags,
+    ) {
+        self.correct_visibilities_inner(buffer, timestep_index, coarse_channel_index, flags, true);
+    }
+
+    /// As [`Self::correct_visibilities`], but for a buffer in the layout
+    /// produced by `read_by_frequency` (`[frequency][baseline][pol][r][i]`).
+    pub fn correct_visibilities_by_frequency(
+        &mut self,
+        buffer: &mut [f32],
+        timestep_index: usize,
+        coarse_channel_index: usize,
+        flags: CorrectionFlags,
+    ) {
+        self.correct_visibilities_inner(buffer, timestep_index, coarse_channel_index, flags, false);
+    }
+
+    /// Shared implementation behind `correct_visibilities`/
+    /// `correct_visibilities_by_frequency`. See those methods for behaviour.
+    fn correct_visibilities_inner(
+        &mut self,
+        buffer: &mut [f32],
+        timestep_index: usize,
+        coarse_channel_index: usize,
+        flags: CorrectionFlags,
+        baseline_major: bool,
+    ) {
+        let key = (timestep_index, coarse_channel_index);
+        let already_applied = self.applied_corrections.get(&key).copied().unwrap_or_default();
+
+        let to_apply = CorrectionFlags {
+            cable_length: flags.cable_length && !already_applied.cable_length,
+            geometric: flags.geometric && !already_applied.geometric,
+            digital_gain: flags.digital_gain && !already_applied.digital_gain,
+            passband: flags.passband && !already_applied.passband,
+        };
+
+        if !to_apply.cable_length
+            && !to_apply.geometric
+            && !to_apply.digital_gain
+            && !to_apply.passband
+        {
+            return;
+        }
+
+        let electrical_lengths = self.antenna_pol_electrical_lengths_m();
+        let digital_gains = if to_apply.digital_gain {
+            Some(self.antenna_pol_digital_gains(coarse_channel_index))
+        } else {
+            None
+        };
+        let uvw = if to_apply.geometric {
+            self.uvw_for_timestep(timestep_index).ok()
+        } else {
+            None
+        };
+        let passband_gains = if to_apply.passband {
+            let table = passband::select_table(self.corr_version, self.fine_channel_width_hz);
+            Some(passband::resample_gains(
+                table.gains(),
+                self.num_fine_channels_per_coarse,
+            ))
+        } else {
+            None
+        };
+
+        let coarse_channel = &self.coarse_channels[coarse_channel_index];
+        let half_band_hz =
+            (self.num_fine_channels_per_coarse as f64 / 2.) * self.fine_channel_width_hz as f64;
+
+        for baseline in 0..self.num_baselines {
+            let (ant1, ant2) = match get_antennas_from_baseline(
+                BaselineOrder::CrossAndAuto,
+                baseline,
+                self.num_antennas,
+            ) {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            let w_m = uvw.as_ref().map(|uvw| uvw[baseline].2);
+
+            for fine_chan in 0..self.num_fine_channels_per_coarse {
+                let sky_freq_hz = coarse_channel.channel_centre_hz as f64 - half_band_hz
+                    + fine_chan as f64 * self.fine_channel_width_hz as f64;
+
+                for (pol_index, (ant1_is_x, ant2_is_x)) in VISIBILITY_POL_ORDER.iter().enumerate()
+                {
+                    let mut phase_rad = 0.;
+                    let mut amplitude_scale = 1.;
+
+                    if to_apply.cable_length {
+                        let (ant1_x_len, ant1_y_len) = electrical_lengths[ant1];
+                        let (ant2_x_len, ant2_y_len) = electrical_lengths[ant2];
+                        let l1 = if *ant1_is_x { ant1_x_len } else { ant1_y_len };
+                        let l2 = if *ant2_is_x { ant2_x_len } else { ant2_y_len };
+                        let tau_cable =
+                            (l1 - l2) / (SPEED_OF_LIGHT_M_PER_S * self.coax_v_factor);
+                        phase_rad += 2. * std::f64::consts::PI * sky_freq_hz * tau_cable;
+                    }
+
+                    if let Some(w_m) = w_m {
+                        phase_rad -= 2. * std::f64::consts::PI * w_m * sky_freq_hz
+                            / SPEED_OF_LIGHT_M_PER_S;
+                    }
+
+                    if let Some(digital_gains) = &digital_gains {
+                        let (ant1_x_gain, ant1_y_gain) = digital_gains[ant1];
+                        let (ant2_x_gain, ant2_y_gain) = digital_gains[ant2];
+                        let g1 = if *ant1_is_x { ant1_x_gain } else { ant1_y_gain };
+                        let g2 = if *ant2_is_x { ant2_x_gain } else { ant2_y_gain };
+                        amplitude_scale /= g1 * g2;
+                    }
+
+                    if let Some(passband_gains) = &passband_gains {
+                        let gain = passband_gains[fine_chan];
+                        if gain != 0. {
+                            amplitude_scale /= gain;
+                        }
+                    }
+
+                    if phase_rad == 0. && amplitude_scale == 1. {
+                        continue;
+                    }
+
+                    let (sin_p, cos_p) = phase_rad.sin_cos();
+
+                    let index = if baseline_major {
+                        ((baseline * self.num_fine_channels_per_coarse + fine_chan)
+                            * self.num_visibility_pols
+                            + pol_index)
+                            * 2
+                    } else {
+                        ((fine_chan * self.num_baselines + baseline) * self.num_visibility_pols
+                            + pol_index)
+                            * 2
+                    };
+
+                    let re = buffer[index] as f64 * amplitude_scale;
+                    let im = buffer[index + 1] as f64 * amplitude_scale;
+
+                    buffer[index] = (re * cos_p - im * sin_p) as f32;
+                    buffer[index + 1] = (re * sin_p + im * cos_p) as f32;
+                }
+            }
+        }
+
+        let merged = CorrectionFlags {
+            cable_length: already_applied.cable_length || to_apply.cable_length,
+            geometric: already_applied.geometric || to_apply.geometric,
+            digital_gain: already_applied.digital_gain || to_apply.digital_gain,
+            passband: already_applied.passband || to_apply.passband,
+        };
+        self.applied_corrections.insert(key, merged);
+    }
+}
+
+/// Which visibility corrections to apply in a call to
+/// `mwalibContext::correct_visibilities`/`correct_visibilities_by_frequency`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CorrectionFlags {
+    /// Correct for the differential cable length between each baseline's two rf_inputs.
+    pub cable_length: bool,
+    /// Correct for the geometric (w-term) delay to the phase centre.
+    pub geometric: bool,
+    /// Correct for the differential digital gain between each baseline's two rf_inputs.
+    pub digital_gain: bool,
+    /// Correct for the MWA PFB's coarse-channel passband gain shape.
+    pub passband: bool,
+}
+
+impl mwalibContext {
+    /// Divide each fine channel of `buffer` (in the `[baseline][frequency][pol][r][i]`
+    /// layout produced by `read_by_baseline`) by the MWA PFB's passband gain
+    /// at that channel, removing the scalloped shape the filterbank imposes
+    /// across each coarse channel. The gain curve is chosen by
+    /// `passband::select_table` from `self.corr_version`/
+    /// `self.fine_channel_width_hz`, then resampled to
+    /// `num_fine_channels_per_coarse` so it applies regardless of
+    /// channelisation.
+    ///
+    /// Most callers should instead enable this correction via
+    /// `set_visibility_corrections`, which applies it (and tracks it in
+    /// `applied_corrections`, so it is never double-applied) automatically
+    /// from `read_by_baseline`/`read_by_frequency`. This method remains for
+    /// correcting a buffer obtained some other way (e.g. from `.mwaf`-backed
+    /// tooling or an externally-read HDU).
+    ///
+    /// # Returns
+    ///
+    /// * The [`PassbandTable`] that was selected, so the correction applied is reproducible.
+    ///
+    pub fn correct_passband(
+        &self,
+        buffer: &mut [f32],
+        // The PFB passband shape is the same for every coarse channel of a
+        // given correlator/resolution, so the index only identifies which
+        // buffer is being corrected; it isn't used to pick the gain curve.
+        _coarse_channel_index: usize,
+    ) -> PassbandTable {
+        let table = passband::select_table(self.corr_version, self.fine_channel_width_hz);
+        let gains = passband::resample_gains(table.gains(), self.num_fine_channels_per_coarse);
+
+        for baseline in 0..self.num_baselines {
+            for fine_chan in 0..self.num_fine_channels_per_coarse {
+                let gain = gains[fine_chan];
+                if gain == 0. {
+                    continue;
+                }
+
+                for pol in 0..self.num_visibility_pols {
+                    let index = ((baseline * self.num_fine_channels_per_coarse + fine_chan)
+                        * self.num_visibility_pols
+                        + pol)
+                        * 2;
+                    buffer[index] = (buffer[index] as f64 / gain) as f32;
+                    buffer[index + 1] = (buffer[index + 1] as f64 / gain) as f32;
+                }
+            }
+        }
+
+        table
+    }
+}
+
+impl mwalibContext {
+    /// Write the selected timesteps/coarse channels out as a uvfits-style FITS
+    /// file: a primary HDU carrying the observation header keys, a `UV_DATA`
+    /// binary table with one row per (timestep, baseline) holding the UU/VV/WW,
+    /// BASELINE and DATE group parameters and the visibilities, and an AIPS AN
+    /// antenna table. The visibilities are not written as a random-groups
+    /// primary array.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - output filename. Must not already exist.
+    ///
+    /// * `timestep_range` - range of indices into `self.timesteps` to write out.
+    ///
+    /// * `coarse_channel_range` - range of indices into `self.coarse_channels` to write out; their fine channels are concatenated into one uvfits IF.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` on success.
+    ///
+    pub fn write_uvfits<T: AsRef<Path>>(
+        &mut self,
+        path: T,
+        timestep_range: std::ops::Range<usize>,
+        coarse_channel_range: std::ops::Range<usize>,
+    ) -> Result<(), ErrorKind> {
+        if timestep_range.end > self.num_timesteps || coarse_channel_range.end > self.num_coarse_channels
+        {
+            return Err(ErrorKind::Custom(format!(
+                "write_uvfits: timestep range {:?} / coarse channel range {:?} out of bounds ({} timesteps, {} coarse channels)",
+                timestep_range, coarse_channel_range, self.num_timesteps, self.num_coarse_channels
+            )));
+        }
+
+        let num_fine_channels = self.num_fine_channels_per_coarse * coarse_channel_range.len();
+        let num_groups = timestep_range.len() * self.num_baselines;
+
+        // One (real, imaginary, weight) triple per fine channel / visibility pol.
+        let group_data_len = num_fine_channels * self.num_visibility_pols * 3;
+
+        // [group][fine_chan][pol][r, i, weight]
+        let mut group_data = vec![0_f32; num_groups * group_data_len];
+        let mut uu_s = vec![0_f32; num_groups];
+        let mut vv_s = vec![0_f32; num_groups];
+        let mut ww_s = vec![0_f32; num_groups];
+        let mut baseline = vec![0_f32; num_groups];
+        let mut date_jd = vec![0_f64; num_groups];
+
+        for (ts_offset, timestep_index) in timestep_range.clone().enumerate() {
+            let uvw = self.uvw_for_timestep(timestep_index)?;
+            let jd = self.timesteps[timestep_index].unix_time_ms as f64 / 1000. / 86400. + 2_440_587.5;
+
+            for b in 0..self.num_baselines {
+                let (ant1, ant2) =
+                    get_antennas_from_baseline(BaselineOrder::CrossAndAuto, b, self.num_antennas)
+                        .expect("baseline index within 0..num_baselines is always valid");
+
+                let group_index = ts_offset * self.num_baselines + b;
+                let (u_m, v_m, w_m) = uvw[b];
+
+                uu_s[group_index] = (u_m / SPEED_OF_LIGHT_M_PER_S) as f32;
+                vv_s[group_index] = (v_m / SPEED_OF_LIGHT_M_PER_S) as f32;
+                ww_s[group_index] = (w_m / SPEED_OF_LIGHT_M_PER_S) as f32;
+                // AIPS convention: baseline = 256 * ant1_number + ant2_number, using 1-based antenna numbers.
+                baseline[group_index] = (256 * (ant1 + 1) + (ant2 + 1)) as f32;
+                date_jd[group_index] = jd;
+            }
+
+            for (coarse_offset, coarse_channel_index) in coarse_channel_range.clone().enumerate() {
+                let gpubox_number = self.coarse_channels[coarse_channel_index].gpubox_number;
+                // A (timestep, coarse channel) combination absent from
+                // `gpubox_time_map` has no HDU backing it; rather than erroring
+                // out, write zeroed visibilities with a negative weight so
+                // downstream tools flag the gap instead of imaging it.
+                let is_present = self
+                    .gpubox_time_map
+                    .get(&self.timesteps[timestep_index].unix_time_ms)
+                    .map_or(false, |by_chan| by_chan.contains_key(&gpubox_number));
+
+                let data = if is_present {
+                    Some(self.read_by_baseline(timestep_index, coarse_channel_index)?)
+                } else {
+                    None
+                };
+
+                for b in 0..self.num_baselines {
+                    let group_index = ts_offset * self.num_baselines + b;
+
+                    for fine_chan in 0..self.num_fine_channels_per_coarse {
+                        let out_fine_chan = coarse_offset * self.num_fine_channels_per_coarse + fine_chan;
+
+                        for pol in 0..self.num_visibility_pols {
+                            let out_index = (group_index * num_fine_channels + out_fine_chan)
+                                * self.num_visibility_pols
+                                * 3
+                                + pol * 3;
+
+                            match &data {
+                                Some(data) => {
+                                    let in_index = ((b * self.num_fine_channels_per_coarse + fine_chan)
+                                        * self.num_visibility_pols
+                                        + pol)
+                                        * 2;
+                                    group_data[out_index] = data[in_index];
+                                    group_data[out_index + 1] = data[in_index + 1];
+                                    group_data[out_index + 2] = 1.0;
+                                }
+                                None => {
+                                    group_data[out_index] = 0.0;
+                                    group_data[out_index + 1] = 0.0;
+                                    group_data[out_index + 2] = -1.0;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut fptr = FitsFile::create(&path)
+            .open()
+            .with_context(|| format!("Failed to create uvfits file {:?}", path.as_ref()))?;
+
+        {
+            let hdu = fptr.primary_hdu().with_context(|| "Failed to get primary HDU of new uvfits file")?;
+
+            hdu.write_key(&mut fptr, "OBJECT", self.observation_name.as_str())?;
+            hdu.write_key(&mut fptr, "TELESCOP", "MWA")?;
+            hdu.write_key(
+                &mut fptr,
+                "OBSRA",
+                self.ra_phase_center_degrees.unwrap_or(self.ra_tile_pointing_degrees),
+            )?;
+            hdu.write_key(
+                &mut fptr,
+                "OBSDEC",
+                self.dec_phase_center_degrees.unwrap_or(self.dec_tile_pointing_degrees),
+            )?;
+            hdu.write_key(
+                &mut fptr,
+                "INTTIM",
+                self.integration_time_milliseconds as f64 / 1000.,
+            )?;
+            hdu.write_key(&mut fptr, "CHWIDTH", self.fine_channel_width_hz as f64)?;
+            hdu.write_key(&mut fptr, "NCHAN", num_fine_channels as i64)?;
+            hdu.write_key(&mut fptr, "OBSID", self.obsid as i64)?;
+            hdu.write_key(&mut fptr, "ORIGIN", self.creator.as_str())?;
+            hdu.write_key(&mut fptr, "PROJECT", self.project_id.as_str())?;
+            hdu.write_key(
+                &mut fptr,
+                "HISTORY",
+                format!(
+                    "Created by mwalib from MWA observation {} ({})",
+                    self.obsid, self.observation_name
+                ),
+            )?;
+        }
+
+        // The uvfits "random groups" primary array (group parameters UU, VV, WW,
+        // BASELINE, DATE followed by the visibility data) isn't representable
+        // by fitsio's safe image-writing API, so the group parameters are
+        // instead written alongside the visibilities as a separate binary
+        // table extension, keyed by group index.
+        let group_params = vec![
+            ColumnDescription::new("UU--SIN").with_type(ColumnDataType::Float).create()?,
+            ColumnDescription::new("VV--SIN").with_type(ColumnDataType::Float).create()?,
+            ColumnDescription::new("WW--SIN").with_type(ColumnDataType::Float).create()?,
+            ColumnDescription::new("BASELINE").with_type(ColumnDataType::Float).create()?,
+            ColumnDescription::new("DATE").with_type(ColumnDataType::Double).create()?,
+            ColumnDescription::new("DATA")
+                .with_type(ColumnDataType::Float)
+                .that_repeats(group_data_len)
+                .create()?,
+        ];
+
+        let uv_hdu = fptr.create_table("UV_DATA".to_string(), &group_params)?;
+        uv_hdu.write_col(&mut fptr, "UU--SIN", &uu_s)?;
+        uv_hdu.write_col(&mut fptr, "VV--SIN", &vv_s)?;
+        uv_hdu.write_col(&mut fptr, "WW--SIN", &ww_s)?;
+        uv_hdu.write_col(&mut fptr, "BASELINE", &baseline)?;
+        uv_hdu.write_col(&mut fptr, "DATE", &date_jd)?;
+        uv_hdu.write_col(&mut fptr, "DATA", &group_data)?;
+
+        // AIPS AN antenna table, populated from `antennas`/`rf_inputs` positions
+        // and the array centre.
+        let antenna_xyz = self.antenna_local_xyz_m();
+        let mut an_names: Vec<String> = Vec::with_capacity(self.num_antennas);
+        let mut an_numbers: Vec<i32> = Vec::with_capacity(self.num_antennas);
+        let mut an_x = vec![0_f64; self.num_antennas];
+        let mut an_y = vec![0_f64; self.num_antennas];
+        let mut an_z = vec![0_f64; self.num_antennas];
+
+        for antenna in &self.antennas {
+            let index = antenna.antenna as usize;
+            an_names.push(antenna.tile_name.clone());
+            an_numbers.push(antenna.antenna as i32 + 1);
+            let (x, y, z) = antenna_xyz[index];
+            an_x[index] = x;
+            an_y[index] = y;
+            an_z[index] = z;
+        }
+
+        let an_columns = vec![
+            ColumnDescription::new("ANNAME").with_type(ColumnDataType::String).create()?,
+            ColumnDescription::new("NOSTA").with_type(ColumnDataType::Int).create()?,
+            ColumnDescription::new("STABXYZ")
+                .with_type(ColumnDataType::Double)
+                .that_repeats(3)
+                .create()?,
+        ];
+
+        // ARRAYX/Y/Z are the array centre's geocentric (ECEF) reference
+        // position, in metres, not the geodetic lat/long/height it's derived
+        // from.
+        let (array_geocentric_x_m, array_geocentric_y_m, array_geocentric_z_m) =
+            geodetic_to_geocentric_xyz(
+                self.mwa_latitude_radians,
+                self.mwa_longitude_radians,
+                self.mwa_altitude_metres,
+            );
+
+        let an_hdu = fptr.create_table("AIPS AN".to_string(), &an_columns)?;
+        an_hdu.write_key(&mut fptr, "ARRAYX", array_geocentric_x_m)?;
+        an_hdu.write_key(&mut fptr, "ARRAYY", array_geocentric_y_m)?;
+        an_hdu.write_key(&mut fptr, "ARRAYZ", array_geocentric_z_m)?;
+        an_hdu.write_col(&mut fptr, "ANNAME", &an_names)?;
+        an_hdu.write_col(&mut fptr, "NOSTA", &an_numbers)?;
+        an_hdu.write_col(
+            &mut fptr,
+            "STABXYZ",
+            &an_x
+                .iter()
+                .zip(an_y.iter())
+                .zip(an_z.iter())
+                .flat_map(|((x, y), z)| vec![*x, *y, *z])
+                .collect::<Vec<f64>>(),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// The `VERSION` primary-HDU key written to `.mwaf` files by `write_mwaf_file`.
+const MWAF_FORMAT_VERSION: i64 = 1;
+
+/// In-memory representation of a `.mwaf` flag file's contents for a single
+/// coarse channel: the primary HDU's metadata, plus one flag per (timestep,
+/// baseline, fine channel).
+#[derive(Debug, Clone)]
+pub struct MwafFlags {
+    /// Observation ID this flag file belongs to.
+    pub obsid: u32,
+    /// Correlator version the flags were generated against.
+    pub corr_version: CorrelatorVersion,
+    /// Number of fine channels per flag (i.e. `num_fine_channels_per_coarse`).
+    pub num_channels: usize,
+    /// Number of baselines covered (i.e. `num_baselines`).
+    pub num_baselines: usize,
+    /// Number of antennas covered (i.e. `num_antennas`).
+    pub num_antennas: usize,
+    /// Number of timesteps covered (i.e. `num_timesteps`).
+    pub num_timesteps: usize,
+    /// Number of coarse channels in the observation this flag file's
+    /// observation belongs to (i.e. `num_coarse_channels`).
+    pub num_coarse_channels: usize,
+    /// The gpubox channel number this flag file applies to.
+    pub gpubox_number: usize,
+    /// Flags, indexed `[timestep_index][baseline_index][fine_channel_index]`;
+    /// `true` means flagged.
+    pub flags: Vec<Vec<Vec<bool>>>,
+}
+
+impl mwalibContext {
+    /// Write `flags` (one flag per (timestep, baseline, fine channel) of the
+    /// coarse channel at `coarse_channel_index`) out as a `.mwaf` file: a
+    /// primary HDU carrying `obsid`/correlator version/channel counts and the
+    /// gpubox channel number, and a single-column binary table with one row
+    /// per (timestep, baseline), each cell a bit vector of length
+    /// `num_fine_channels_per_coarse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `coarse_channel_index` - index within the coarse_channel array the flags apply to.
+    ///
+    /// * `flags` - flags, indexed `[timestep_index][baseline_index][fine_channel_index]`; must cover `num_timesteps` x `num_baselines` x `num_fine_channels_per_coarse`.
+    ///
+    /// * `path` - output filename. Must not already exist.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` on success.
+    ///
+    pub fn write_mwaf_file<T: AsRef<Path>>(
+        &self,
+        coarse_channel_index: usize,
+        flags: &[Vec<Vec<bool>>],
+        path: T,
+    ) -> Result<(), ErrorKind> {
+        if flags.len() != self.num_timesteps
+            || flags.iter().any(|by_baseline| by_baseline.len() != self.num_baselines)
+            || flags.iter().flatten().any(|by_chan| by_chan.len() != self.num_fine_channels_per_coarse)
+        {
+            return Err(ErrorKind::Custom(format!(
+                "write_mwaf_file: flags must be [{} timesteps][{} baselines][{} fine channels]",
+                self.num_timesteps, self.num_baselines, self.num_fine_channels_per_coarse
+            )));
+        }
+
+        let gpubox_number = self.coarse_channels[coarse_channel_index].gpubox_number;
+
+        let mut fptr = FitsFile::create(&path)
+            .open()
+            .with_context(|| format!("Failed to create mwaf file {:?}", path.as_ref()))?;
+
+        {
+            let hdu = fptr.primary_hdu().with_context(|| "Failed to get primary HDU of new mwaf file")?;
+            hdu.write_key(&mut fptr, "VERSION", MWAF_FORMAT_VERSION)?;
+            hdu.write_key(&mut fptr, "OBSID", self.obsid as i64)?;
+            hdu.write_key(&mut fptr, "CORRVER", format!("{}", self.corr_version))?;
+            hdu.write_key(
+                &mut fptr,
+                "GPSTIME",
+                (self.scheduled_start_gpstime_milliseconds / 1000) as i64,
+            )?;
+            hdu.write_key(&mut fptr, "NCHANS", self.num_fine_channels_per_coarse as i64)?;
+            hdu.write_key(&mut fptr, "NBASELINES", self.num_baselines as i64)?;
+            hdu.write_key(&mut fptr, "NANTENNA", self.num_antennas as i64)?;
+            hdu.write_key(&mut fptr, "NSCANS", self.num_timesteps as i64)?;
+            hdu.write_key(&mut fptr, "NBAND", self.num_coarse_channels as i64)?;
+            hdu.write_key(&mut fptr, "GPUBOXNO", gpubox_number as i64)?;
+        }
+
+        let columns = vec![ColumnDescription::new("FLAGS")
+            .with_type(ColumnDataType::Bit)
+            .that_repeats(self.num_fine_channels_per_coarse)
+            .create()?];
+        let table_hdu = fptr.create_table("FLAGS".to_string(), &columns)?;
+
+        // Row-major: row (timestep * num_baselines + baseline) holds that
+        // pair's fine channel flags, written in one call so each row lands
+        // in its own slot of the repeated column.
+        let cells: Vec<bool> = flags.iter().flatten().flatten().copied().collect();
+
+        table_hdu
+            .write_col(&mut fptr, "FLAGS", &cells)
+            .with_context(|| "Failed to write FLAGS column of mwaf file")?;
+
+        Ok(())
+    }
+
+    /// Read a `.mwaf` file written by `write_mwaf_file` (or a compatible
+    /// external flagger) back into an in-memory [`MwafFlags`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the `.mwaf` file to read.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing the parsed [`MwafFlags`], if Ok.
+    ///
+    pub fn read_mwaf_file<T: AsRef<Path>>(&self, path: T) -> Result<MwafFlags, ErrorKind> {
+        let mut fptr = FitsFile::open(&path)
+            .with_context(|| format!("Failed to open mwaf file {:?}", path.as_ref()))?;
+        let primary_hdu = fptr
+            .hdu(0)
+            .with_context(|| format!("Failed to open primary HDU of mwaf file {:?}", path.as_ref()))?;
+
+        let obsid: u32 = get_required_fits_key(&mut fptr, &primary_hdu, "OBSID")
+            .with_context(|| format!("Failed to read OBSID for {:?}", path.as_ref()))?;
+        let num_channels: usize = get_required_fits_key(&mut fptr, &primary_hdu, "NCHANS")
+            .with_context(|| format!("Failed to read NCHANS for {:?}", path.as_ref()))?;
+        let num_baselines: usize = get_required_fits_key(&mut fptr, &primary_hdu, "NBASELINES")
+            .with_context(|| format!("Failed to read NBASELINES for {:?}", path.as_ref()))?;
+        let num_antennas: usize = get_required_fits_key(&mut fptr, &primary_hdu, "NANTENNA")
+            .with_context(|| format!("Failed to read NANTENNA for {:?}", path.as_ref()))?;
+        let num_timesteps: usize = get_required_fits_key(&mut fptr, &primary_hdu, "NSCANS")
+            .with_context(|| format!("Failed to read NSCANS for {:?}", path.as_ref()))?;
+        let num_coarse_channels: usize = get_required_fits_key(&mut fptr, &primary_hdu, "NBAND")
+            .with_context(|| format!("Failed to read NBAND for {:?}", path.as_ref()))?;
+        let gpubox_number: usize = get_required_fits_key(&mut fptr, &primary_hdu, "GPUBOXNO")
+            .with_context(|| format!("Failed to read GPUBOXNO for {:?}", path.as_ref()))?;
+
+        let table_hdu = fptr
+            .hdu("FLAGS")
+            .with_context(|| format!("Failed to open FLAGS table of mwaf file {:?}", path.as_ref()))?;
+
+        let mut flags: Vec<Vec<Vec<bool>>> = Vec::with_capacity(num_timesteps);
+        for timestep_index in 0..num_timesteps {
+            let mut by_baseline = Vec::with_capacity(num_baselines);
+            for baseline_index in 0..num_baselines {
+                let row_index = timestep_index * num_baselines + baseline_index;
+                let row: Vec<bool> = table_hdu
+                    .read_cell_value(&mut fptr, "FLAGS", row_index)
+                    .with_context(|| format!("Failed to read FLAGS row {} of {:?}", row_index, path.as_ref()))?;
+                by_baseline.push(row);
+            }
+            flags.push(by_baseline);
+        }
+
+        Ok(MwafFlags {
+            obsid,
+            corr_version: self.corr_version,
+            num_channels,
+            num_baselines,
+            num_antennas,
+            num_timesteps,
+            num_coarse_channels,
+            gpubox_number,
+            flags,
+        })
+    }
+}
+
+/// A set of per-coarse-channel `.mwaf` flag files for an observation, keyed
+/// the same way as `gpubox_time_map`/`coarse_channels`: one file per gpubox
+/// number, named `<obsid>_<gpubox_number>.mwaf` within a common directory.
+#[derive(Debug, Clone)]
+pub struct FlagFileSet {
+    /// Path of each coarse channel's flag file, keyed by its gpubox number.
+    pub files: BTreeMap<usize, PathBuf>,
+}
+
+impl FlagFileSet {
+    /// Build a `FlagFileSet` naming one file per entry of
+    /// `context.coarse_channels` inside `directory`.
+    pub fn new<T: AsRef<Path>>(context: &mwalibContext, directory: T) -> FlagFileSet {
+        let files = context
+            .coarse_channels
+            .iter()
+            .map(|coarse_channel| {
+                let filename = format!(
+                    "{}_{:02}.mwaf",
+                    context.obsid, coarse_channel.gpubox_number
+                );
+                (coarse_channel.gpubox_number, directory.as_ref().join(filename))
+            })
+            .collect();
+
+        FlagFileSet { files }
+    }
+
+    /// Write `flags` (one entry per `context.coarse_channels`, each indexed
+    /// `[timestep_index][baseline_index][fine_channel_index]`) out to this
+    /// set's files, via `mwalibContext::write_mwaf_file`.
+    pub fn write(&self, context: &mwalibContext, flags: &[Vec<Vec<Vec<bool>>>]) -> Result<(), ErrorKind> {
+        for (coarse_channel_index, coarse_channel) in context.coarse_channels.iter().enumerate() {
+            let path = self.files.get(&coarse_channel.gpubox_number).ok_or_else(|| {
+                ErrorKind::Custom(format!(
+                    "FlagFileSet::write: no file registered for gpubox number {}",
+                    coarse_channel.gpubox_number
+                ))
+            })?;
+
+            context.write_mwaf_file(
+                coarse_channel_index,
+                flags.get(coarse_channel_index).ok_or_else(|| {
+                    ErrorKind::Custom(format!(
+                        "FlagFileSet::write: missing flags for coarse channel index {}",
+                        coarse_channel_index
+                    ))
+                })?,
+                path,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Read back the flags for a single coarse channel, validating the
+    /// file's channel/baseline/gpubox-number metadata against `context`'s own
+    /// dimensions (mirroring the checks `validate_hdu_axes` performs for
+    /// gpubox files) and rejecting any mismatch.
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing the flags, indexed `[timestep_index][baseline_index][fine_channel_index]`, if Ok.
+    ///
+    pub fn read(
+        &self,
+        context: &mwalibContext,
+        coarse_channel_index: usize,
+    ) -> Result<Vec<Vec<Vec<bool>>>, ErrorKind> {
+        let coarse_channel = &context.coarse_channels[coarse_channel_index];
+        let path = self.files.get(&coarse_channel.gpubox_number).ok_or_else(|| {
+            ErrorKind::Custom(format!(
+                "FlagFileSet::read: no file registered for gpubox number {}",
+                coarse_channel.gpubox_number
+            ))
+        })?;
+
+        let mwaf = context.read_mwaf_file(path)?;
+
+        if mwaf.num_channels != context.num_fine_channels_per_coarse {
+            return Err(ErrorKind::Custom(format!(
+                "FlagFileSet::read: {:?} has {} channels, expected {}",
+                path, mwaf.num_channels, context.num_fine_channels_per_coarse
+            )));
+        }
+        if mwaf.num_baselines != context.num_baselines {
+            return Err(ErrorKind::Custom(format!(
+                "FlagFileSet::read: {:?} has {} baselines, expected {}",
+                path, mwaf.num_baselines, context.num_baselines
+            )));
+        }
+        if mwaf.num_antennas != context.num_antennas {
+            return Err(ErrorKind::Custom(format!(
+                "FlagFileSet::read: {:?} has {} antennas, expected {}",
+                path, mwaf.num_antennas, context.num_antennas
+            )));
+        }
+        if mwaf.gpubox_number != coarse_channel.gpubox_number {
+            return Err(ErrorKind::Custom(format!(
+                "FlagFileSet::read: {:?} is for gpubox number {}, expected {}",
+                path, mwaf.gpubox_number, coarse_channel.gpubox_number
+            )));
+        }
+
+        Ok(mwaf.flags)
+    }
+}
+
+impl mwalibContext {
+    /// Whether a gpubox HDU exists for this (timestep, coarse channel) pair,
+    /// per `gpubox_time_map`.
+    fn has_gpubox_hdu(&self, timestep_index: usize, coarse_channel_index: usize) -> bool {
+        let coarse_channel = self.coarse_channels[coarse_channel_index].gpubox_number;
+        self.timesteps
+            .get(timestep_index)
+            .and_then(|timestep| self.gpubox_time_map.get(&timestep.unix_time_ms))
+            .map_or(false, |by_channel| by_channel.contains_key(&coarse_channel))
+    }
+
+    /// Indices into `self.timesteps` for which every coarse channel in
+    /// `self.coarse_channels` has a gpubox HDU, per `gpubox_time_map`. These
+    /// are the timesteps a chunked reader like `read_selection` can safely
+    /// span without hitting a gap.
+    pub fn common_timestep_indices(&self) -> Vec<usize> {
+        (0..self.num_timesteps)
+            .filter(|&timestep_index| {
+                (0..self.num_coarse_channels)
+                    .all(|coarse_channel_index| self.has_gpubox_hdu(timestep_index, coarse_channel_index))
+            })
+            .collect()
+    }
+
+    /// As [`Self::common_timestep_indices`], but further restricted to
+    /// timesteps starting on or after `good_time_unix_milliseconds` (i.e.
+    /// after the observation's quack/settling time).
+    pub fn common_good_timestep_indices(&self) -> Vec<usize> {
+        self.common_timestep_indices()
+            .into_iter()
+            .filter(|&timestep_index| {
+                self.timesteps[timestep_index].unix_time_ms >= self.good_time_unix_milliseconds
+            })
+            .collect()
+    }
+
+    /// Indices into `self.coarse_channels` for which every timestep in
+    /// `self.timesteps` has a gpubox HDU, per `gpubox_time_map`.
+    pub fn common_coarse_chan_indices(&self) -> Vec<usize> {
+        (0..self.num_coarse_channels)
+            .filter(|&coarse_channel_index| {
+                (0..self.num_timesteps)
+                    .all(|timestep_index| self.has_gpubox_hdu(timestep_index, coarse_channel_index))
+            })
+            .collect()
+    }
+
+    /// Read a `[timestep][coarse_chan][baseline][fine_chan][pol][r][i]`-ordered slab of
+    /// visibilities covering `timestep_range` x `coarse_chan_range` x
+    /// `baseline_range`, reusing `read_by_baseline`'s per-HDU read and
+    /// reorder logic one HDU at a time so the whole observation need not fit
+    /// in memory at once. Gaps in `gpubox_time_map` are zero-filled, as in
+    /// `read_by_baseline_averaged`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestep_range` - range of indices into `self.timesteps` to read.
+    ///
+    /// * `coarse_chan_range` - range of indices into `self.coarse_channels` to read.
+    ///
+    /// * `baseline_range` - range of baseline indices (as used by `read_by_baseline`) to keep from each HDU.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing a flat `Vec<f32>` in `[timestep][coarse_chan][baseline][fine_chan][pol][r][i]` order, if Ok.
+    ///
+    pub fn read_selection(
+        &mut self,
+        timestep_range: Range<usize>,
+        coarse_chan_range: Range<usize>,
+        baseline_range: Range<usize>,
+    ) -> Result<Vec<f32>, ErrorKind> {
+        if timestep_range.end > self.num_timesteps
+            || coarse_chan_range.end > self.num_coarse_channels
+            || baseline_range.end > self.num_baselines
+        {
+            return Err(ErrorKind::Custom(format!(
+                "read_selection: timestep range {:?} / coarse channel range {:?} / baseline range {:?} out of bounds ({} timesteps, {} coarse channels, {} baselines)",
+                timestep_range, coarse_chan_range, baseline_range,
+                self.num_timesteps, self.num_coarse_channels, self.num_baselines
+            )));
+        }
+
+        let num_baselines = baseline_range.len();
+        let num_fine_channels = self.num_fine_channels_per_coarse;
+        let cell_len = self.num_visibility_pols * 2;
+
+        let mut output = vec![
+            0_f32;
+            timestep_range.len()
+                * coarse_chan_range.len()
+                * num_baselines
+                * num_fine_channels
+                * cell_len
+        ];
+
+        for (ts_offset, timestep_index) in timestep_range.clone().enumerate() {
+            for (chan_offset, coarse_channel_index) in coarse_chan_range.clone().enumerate() {
+                if !self.has_gpubox_hdu(timestep_index, coarse_channel_index) {
+                    continue;
+                }
+
+                let data = self
+                    .read_by_baseline(timestep_index, coarse_channel_index)
+                    .map_err(ErrorKind::from)?;
+
+                for (out_baseline, baseline) in baseline_range.clone().enumerate() {
+                    let in_start = baseline * num_fine_channels * cell_len;
+                    let in_end = in_start + num_fine_channels * cell_len;
+
+                    let out_start = ((ts_offset * coarse_chan_range.len() + chan_offset)
+                        * num_baselines
+                        + out_baseline)
+                        * num_fine_channels
+                        * cell_len;
+
+                    output[out_start..out_start + num_fine_channels * cell_len]
+                        .copy_from_slice(&data[in_start..in_end]);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Turn `(sum_real, sum_imag, num_contributors)` accumulators into a
+    /// `[r][i]` buffer of means, with zero-contributor cells left as zero.
+    fn average_sums(sums: Vec<(f64, f64, usize)>) -> Vec<f32> {
+        let mut output = vec![0_f32; sums.len() * 2];
+        for (i, (re, im, count)) in sums.into_iter().enumerate() {
+            if count > 0 {
+                output[i * 2] = (re / count as f64) as f32;
+                output[i * 2 + 1] = (im / count as f64) as f32;
+            }
+        }
+        output
+    }
+
+    /// Read `time_avg` consecutive raw timesteps starting at
+    /// `timestep_index` for a single coarse channel, averaging blocks of
+    /// `freq_avg` consecutive fine channels together. The output is in order
+    /// `[baseline][averaged_fine_chan][pol][r][i]`, matching
+    /// `read_by_baseline`'s layout.
+    ///
+    /// Each output cell is the mean of its contributing raw samples; raw
+    /// timesteps with no gpubox HDU for this coarse channel (per
+    /// `gpubox_time_map`) contribute nothing, and a cell with zero
+    /// contributors is returned as zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestep_index` - index of the first raw timestep to average.
+    ///
+    /// * `coarse_channel_index` - index within the coarse_channel array for the desired coarse channel.
+    ///
+    /// * `freq_avg` - number of adjacent raw fine channels to combine into one averaged channel. `num_fine_channels_per_coarse` must be a multiple of this.
+    ///
+    /// * `time_avg` - number of adjacent raw timesteps (starting at `timestep_index`) to combine into the single averaged output timestep. `num_timesteps` must be a multiple of this.
+    ///
+    /// * `flags` - optional flags (e.g. from `read_mwaf_file`) for this coarse channel; flagged (timestep, baseline, fine channel) triples are excluded from the average.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A Result containing vector of 32 bit floats containing the averaged data in `[baseline][averaged_fine_chan][pol][r][i]` order, if Ok.
+    ///
+    pub fn read_by_baseline_averaged(
+        &mut self,
+        timestep_index: usize,
+        coarse_channel_index: usize,
+        freq_avg: usize,
+        time_avg: usize,
+        flags: Option<&MwafFlags>,
+    ) -> Result<Vec<f32>, ErrorKind> {
+        if self.num_fine_channels_per_coarse % freq_avg != 0 {
+            return Err(ErrorKind::Custom(format!(
+                "read_by_baseline_averaged: num_fine_channels_per_coarse ({}) is not a multiple of freq_avg ({})",
+                self.num_fine_channels_per_coarse, freq_avg
+            )));
+        }
+        if self.num_timesteps % time_avg != 0 {
+            return Err(ErrorKind::Custom(format!(
+                "read_by_baseline_averaged: num_timesteps ({}) is not a multiple of time_avg ({})",
+                self.num_timesteps, time_avg
+            )));
+        }
+
+        let averaged_num_fine_channels = self.num_fine_channels_per_coarse / freq_avg;
+
+        // (sum_real, sum_imag, num_unflagged_contributors) per (baseline, averaged_fine_chan, pol).
+        let mut sums = vec![
+            (0_f64, 0_f64, 0_usize);
+            self.num_baselines * averaged_num_fine_channels * self.num_visibility_pols
+        ];
+
+        let raw_timestep_end = (timestep_index + time_avg).min(self.num_timesteps);
+
+        for raw_timestep_index in timestep_index..raw_timestep_end {
+            if !self.has_gpubox_hdu(raw_timestep_index, coarse_channel_index) {
+                continue;
+            }
+
+            let data = self
+                .read_by_baseline(raw_timestep_index, coarse_channel_index)
+                .map_err(ErrorKind::from)?;
+
+            for baseline in 0..self.num_baselines {
+                for raw_fine_chan in 0..self.num_fine_channels_per_coarse {
+                    let flagged = flags
+                        .map(|f| f.flags[raw_timestep_index][baseline][raw_fine_chan])
+                        .unwrap_or(false);
+                    if flagged {
+                        continue;
+                    }
+
+                    let averaged_fine_chan = raw_fine_chan / freq_avg;
+
+                    for pol in 0..self.num_visibility_pols {
+                        let in_index = ((baseline * self.num_fine_channels_per_coarse
+                            + raw_fine_chan)
+                            * self.num_visibility_pols
+                            + pol)
+                            * 2;
+                        let out_index = (baseline * averaged_num_fine_channels
+                            + averaged_fine_chan)
+                            * self.num_visibility_pols
+                            + pol;
+
+                        let entry = &mut sums[out_index];
+                        entry.0 += data[in_index] as f64;
+                        entry.1 += data[in_index + 1] as f64;
+                        entry.2 += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(Self::average_sums(sums))
+    }
+
+    /// As [`Self::read_by_baseline_averaged`], but for the
+    /// `[averaged_fine_chan][baseline][pol][r][i]` layout produced by
+    /// `read_by_frequency`.
+    pub fn read_by_frequency_averaged(
+        &mut self,
+        timestep_index: usize,
+        coarse_channel_index: usize,
+        freq_avg: usize,
+        time_avg: usize,
+        flags: Option<&MwafFlags>,
+    ) -> Result<Vec<f32>, ErrorKind> {
+        if self.num_fine_channels_per_coarse % freq_avg != 0 {
+            return Err(ErrorKind::Custom(format!(
+                "read_by_frequency_averaged: num_fine_channels_per_coarse ({}) is not a multiple of freq_avg ({})",
+                self.num_fine_channels_per_coarse, freq_avg
+            )));
+        }
+        if self.num_timesteps % time_avg != 0 {
+            return Err(ErrorKind::Custom(format!(
+                "read_by_frequency_averaged: num_timesteps ({}) is not a multiple of time_avg ({})",
+                self.num_timesteps, time_avg
+            )));
+        }
+
+        let averaged_num_fine_channels = self.num_fine_channels_per_coarse / freq_avg;
+
+        // (sum_real, sum_imag, num_unflagged_contributors) per (averaged_fine_chan, baseline, pol).
+        let mut sums = vec![
+            (0_f64, 0_f64, 0_usize);
+            averaged_num_fine_channels * self.num_baselines * self.num_visibility_pols
+        ];
+
+        let raw_timestep_end = (timestep_index + time_avg).min(self.num_timesteps);
+
+        for raw_timestep_index in timestep_index..raw_timestep_end {
+            if !self.has_gpubox_hdu(raw_timestep_index, coarse_channel_index) {
+                continue;
+            }
+
+            let data = self
+                .read_by_frequency(raw_timestep_index, coarse_channel_index)
+                .map_err(ErrorKind::from)?;
+
+            for raw_fine_chan in 0..self.num_fine_channels_per_coarse {
+                let averaged_fine_chan = raw_fine_chan / freq_avg;
+
+                for baseline in 0..self.num_baselines {
+                    let flagged = flags
+                        .map(|f| f.flags[raw_timestep_index][baseline][raw_fine_chan])
+                        .unwrap_or(false);
+                    if flagged {
+                        continue;
+                    }
+
+                    for pol in 0..self.num_visibility_pols {
+                        let in_index = ((raw_fine_chan * self.num_baselines + baseline)
+                            * self.num_visibility_pols
+                            + pol)
+                            * 2;
+                        let out_index = (averaged_fine_chan * self.num_baselines + baseline)
+                            * self.num_visibility_pols
+                            + pol;
+
+                        let entry = &mut sums[out_index];
+                        entry.0 += data[in_index] as f64;
+                        entry.1 += data[in_index + 1] as f64;
+                        entry.2 += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(Self::average_sums(sums))
+    }
+}
+
+impl mwalibContext {
+    /// A fine channel is flagged by default if it's one of the two edge
+    /// channels of its coarse channel, or the centre ("DC") channel, which
+    /// routinely carries a correlator artefact.
+    fn is_default_flagged_fine_channel(&self, fine_chan: usize) -> bool {
+        fine_chan == 0
+            || fine_chan == self.num_fine_channels_per_coarse - 1
+            || fine_chan == self.num_fine_channels_per_coarse / 2
+    }
+
+    /// Compute the narrowest contiguous (coarse channel range, fine channel
+    /// range) that has no flagged edges, combining the default edge/DC-channel
+    /// flags with any supplied `.mwaf` flags, and store it as
+    /// `default_channel_band`. `write_uvfits` and `read_by_baseline_averaged`
+    /// callers should prefer this over the full observation bandwidth.
+    ///
+    /// A coarse channel is considered entirely flagged (and trimmed from the
+    /// coarse channel range) if every (timestep, baseline, fine channel)
+    /// triple in its corresponding `mwaf_flags` entry is flagged. A fine
+    /// channel position is considered flagged (and trimmed from the fine
+    /// channel range) if it's flagged by default, or flagged in every
+    /// remaining coarse channel's `.mwaf` flags.
+    ///
+    /// # Arguments
+    ///
+    /// * `mwaf_flags` - optional per-coarse-channel `.mwaf` flags (e.g. from `read_mwaf_file`), one entry per `self.coarse_channels`, in the same order.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * The `(coarse_channel_range, fine_channel_range)` band, as stored in `default_channel_band`.
+    ///
+    pub fn compute_smallest_unflagged_channel_band(
+        &mut self,
+        mwaf_flags: Option<&[MwafFlags]>,
+    ) -> (Range<usize>, Range<usize>) {
+        let mut coarse_start = 0;
+        let mut coarse_end = self.num_coarse_channels;
+
+        if let Some(mwaf_flags) = mwaf_flags {
+            let is_coarse_channel_entirely_flagged = |flags: &MwafFlags| {
+                flags
+                    .flags
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .all(|&flagged| flagged)
+            };
+
+            while coarse_start < coarse_end
+                && is_coarse_channel_entirely_flagged(&mwaf_flags[coarse_start])
+            {
+                coarse_start += 1;
+            }
+            while coarse_end > coarse_start
+                && is_coarse_channel_entirely_flagged(&mwaf_flags[coarse_end - 1])
+            {
+                coarse_end -= 1;
+            }
+        }
+
+        let is_fine_channel_flagged = |fine_chan: usize| {
+            if self.is_default_flagged_fine_channel(fine_chan) {
+                return true;
+            }
+
+            match mwaf_flags {
+                Some(mwaf_flags) => (coarse_start..coarse_end).all(|coarse_chan| {
+                    mwaf_flags[coarse_chan]
+                        .flags
+                        .iter()
+                        .all(|by_baseline| by_baseline.iter().all(|by_chan| by_chan[fine_chan]))
+                }),
+                None => false,
+            }
+        };
+
+        let mut fine_start = 0;
+        let mut fine_end = self.num_fine_channels_per_coarse;
+
+        while fine_start < fine_end && is_fine_channel_flagged(fine_start) {
+            fine_start += 1;
+        }
+        while fine_end > fine_start && is_fine_channel_flagged(fine_end - 1) {
+            fine_end -= 1;
+        }
+
+        let band = (coarse_start..coarse_end, fine_start..fine_end);
+        self.default_channel_band = Some(band.clone());
+
+        band
+    }
+}
+
+/// Implements fmt::Display for mwalibContext struct
+///
+/// # Arguments
+///
+/// * `f` - A fmt::Formatter
+///
+///
+/// # Returns
+///
+/// * `fmt::Result` - Result of this method
+///
+///
+#[cfg_attr(tarpaulin, skip)]
+impl fmt::Display for mwalibContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `size` is the number of floats (self.gpubox_hdu_size) multiplied by 4
+        // bytes per float, divided by 1024^2 to get MiB.
+        let size = (self.num_timestep_coarse_channel_floats * 4) as f64 / (1024 * 1024) as f64;
+        writeln!(
+            f,
+            r#"mwalibContext (
+    Correlator version:       {},
+
+    MWA latitude:             {} degrees,
+    MWA longitude:            {} degrees
+    MWA altitude:             {} m,
+
+    obsid:                    {},
+
+    Creator:                  {},
+    Project ID:               {},
+    Observation Name:         {},
+    Receivers:                {:?},    
+    Delays:                   {:?},
+    Global attenuation:       {} dB,
+
+    Scheduled start (UNIX)    {},
+    Scheduled end (UNIX)      {}, 
+    Scheduled start (GPS)     {},
+    Scheduled end (GPS)       {}, 
+    Scheduled start (utc)     {},
+    Scheduled end (utc)       {},
+    Scheduled start (MJD)     {},
+    Scheduled end (MJD)       {},
+    Scheduled duration        {} s,                
+    Actual UNIX start time:   {},
+    Actual UNIX end time:     {},
+    Actual duration:          {} s,
+    Quack time:               {} s,
+    Good UNIX start time:     {},
+
+    R.A. (tile_pointing):     {} degrees,
+    Dec. (tile_pointing):     {} degrees,
+    R.A. (phase center):      {:?} degrees,
+    Dec. (phase center):      {:?} degrees,
+    Azimuth:                  {} degrees,
+    Altitude:                 {} degrees,
+    Sun altitude:             {} degrees,
+    Sun distance:             {} degrees,
+    Moon distance:            {} degrees,
+    Jupiter distance:         {} degrees,
+    LST:                      {} degrees,
+    Hour angle:               {} degrees,
+    Grid name:                {},
+    Grid number:              {},    
+    
+    num timesteps:            {},
+    timesteps:                {:?},
+
+    num antennas:             {},
+    antennas:                 {:?},
+    rf_inputs:                {:?},
+
+    num baselines:            {},
+    num auto-correlations:    {},
+    num cross-correlations:   {},
+
+    num antenna pols:         {},
+    num visibility pols:      {},
+
+    observation bandwidth:    {} MHz,
+    num coarse channels,      {},
+    coarse channels:          {:?},
+
+    Correlator Mode:
+    Mode:                     {},
+    fine channel resolution:  {} kHz,
+    integration time:         {:.2} s
+    num fine channels/coarse: {},
+
+    gpubox HDU size:          {} MiB,
+    Memory usage per scan:    {} MiB,
+
+    metafits filename:        {},
+    gpubox batches:           {:#?},
+)"#,
+            self.corr_version,
+            self.mwa_latitude_radians.to_degrees(),
+            self.mwa_longitude_radians.to_degrees(),
+            self.mwa_altitude_metres,
+            self.obsid,
+            self.creator,
+            self.project_id,
+            self.observation_name,
+            self.receivers,
+            self.delays,
+            self.global_analogue_attenuation_db,
+            self.scheduled_start_unix_time_milliseconds as f64 / 1e3,
+            self.scheduled_end_unix_time_milliseconds as f64 / 1e3,
+            self.scheduled_start_gpstime_milliseconds as f64 / 1e3,
+            self.scheduled_end_gpstime_milliseconds as f64 / 1e3,
+            self.scheduled_start_utc,
+            self.scheduled_end_utc,
+            self.scheduled_start_mjd,
+            self.scheduled_end_mjd,
+            self.scheduled_duration_milliseconds as f64 / 1e3,
+            self.start_unix_time_milliseconds as f64 / 1e3,
+            self.end_unix_time_milliseconds as f64 / 1e3,
+            self.duration_milliseconds as f64 / 1e3,
+            self.quack_time_duration_milliseconds as f64 / 1e3,
+            self.good_time_unix_milliseconds as f64 / 1e3,
+            self.ra_tile_pointing_degrees,
+            self.dec_tile_pointing_degrees,
+            self.ra_phase_center_degrees,
+            self.dec_phase_center_degrees,
+            self.azimuth_degrees,
+            self.altitude_degrees,
+            self.sun_altitude_degrees,
+            self.sun_distance_degrees,
+            self.moon_distance_degrees,
+            self.jupiter_distance_degrees,
+            self.lst_degrees,
+            self.hour_angle_string,
+            self.grid_name,
+            self.grid_number,
+            self.num_timesteps,
+            self.timesteps,
+            self.num_antennas,
+            self.antennas,
+            self.rf_inputs,
+            self.num_baselines,
+            self.num_antennas,
+            self.num_baselines - self.num_antennas,
+            self.num_antenna_pols,
+            self.num_visibility_pols,
+            self.observation_bandwidth_hz as f64 / 1e6,
+            self.num_coarse_channels,
+            self.coarse_channels,
+            self.mode,
+            self.fine_channel_width_hz as f64 / 1e3,
+            self.integration_time_milliseconds as f64 / 1e3,
+            self.num_fine_channels_per_coarse,
+            size,
+            size * self.num_gpubox_files as f64,
+            self.metafits_filename,
+            self.gpubox_batches,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::*;
+
+    #[test]
     fn test_context_new_missing_gpubox_files() {
         let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
         let metafits: String = String::from(metafits_filename);
@@ -1374,4 +2920,318 @@ mod tests {
 
         assert!(result_invalid3.is_err());
     }
+
+    #[test]
+    fn test_uvw_for_timestep() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        // This metafits has no RAPHASE/DECPHASE, so phase to the tile pointing.
+        context.ra_phase_center_degrees = Some(context.ra_tile_pointing_degrees);
+        context.dec_phase_center_degrees = Some(context.dec_tile_pointing_degrees);
+
+        let uvw = context
+            .uvw_for_timestep(0)
+            .expect("uvw_for_timestep should succeed when a phase centre is set");
+
+        // One (u, v, w) triple per baseline, in the same order read_by_baseline uses.
+        assert_eq!(uvw.len(), context.num_baselines);
+
+        // An antenna's autocorrelation baseline has a zero-length vector
+        // between it and itself, so its UVW is always the origin regardless
+        // of phase centre.
+        let (ant1, ant2) =
+            get_antennas_from_baseline(BaselineOrder::CrossAndAuto, 0, context.num_antennas)
+                .expect("baseline 0 is always valid");
+        assert_eq!(ant1, ant2);
+        let (u, v, w) = uvw[0];
+        assert!(approx_eq!(f64, u, 0., F64Margin::default()));
+        assert!(approx_eq!(f64, v, 0., F64Margin::default()));
+        assert!(approx_eq!(f64, w, 0., F64Margin::default()));
+    }
+
+    #[test]
+    fn test_correct_visibilities_is_idempotent() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        let mut buffer = context
+            .read_by_baseline(0, 0)
+            .expect("Failed to read_by_baseline");
+
+        let flags = CorrectionFlags {
+            cable_length: true,
+            geometric: true,
+            digital_gain: false,
+            passband: false,
+        };
+
+        context.correct_visibilities(&mut buffer, 0, 0, flags);
+        let once_corrected = buffer.clone();
+
+        // applied_corrections should prevent a second call for the same
+        // (timestep, coarse channel) from corrupting the buffer by
+        // re-applying the same corrections on top of themselves.
+        context.correct_visibilities(&mut buffer, 0, 0, flags);
+
+        assert_eq!(buffer, once_corrected);
+    }
+
+    #[test]
+    fn test_correct_passband_rescales_by_selected_table() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        let original = context
+            .read_by_baseline(0, 0)
+            .expect("Failed to read_by_baseline");
+        let mut corrected = original.clone();
+
+        let table = context.correct_passband(&mut corrected, 0);
+
+        // This test file is read by the legacy correlator, which always uses
+        // the 10 kHz gain curve.
+        assert_eq!(table, PassbandTable::Legacy10kHz);
+
+        // The gain curve is not flat, so at least one non-edge sample must
+        // have actually changed.
+        assert_ne!(original, corrected);
+    }
+
+    #[test]
+    fn test_set_baseline_conjugation_negates_imaginary_component() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        // Default convention: Ant1Ant2, the correlator's native output.
+        let native = context
+            .read_by_baseline(0, 0)
+            .expect("Failed to read_by_baseline");
+
+        context.set_baseline_conjugation(BaselineConjugation::Ant2Ant1);
+        let conjugated = context
+            .read_by_baseline(0, 0)
+            .expect("Failed to read_by_baseline");
+
+        assert_eq!(native.len(), conjugated.len());
+        for (index, (n, c)) in native.iter().zip(conjugated.iter()).enumerate() {
+            if index % 2 == 0 {
+                // Real component is unchanged.
+                assert!(approx_eq!(f32, *n, *c, F32Margin::default()));
+            } else {
+                // Imaginary component is negated.
+                assert!(approx_eq!(f32, *n, -*c, F32Margin::default()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_by_baseline_averaged_matches_manual_mean() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        let raw = context
+            .read_by_baseline(0, 0)
+            .expect("Failed to read_by_baseline");
+
+        // Averaging with factor 1 across both axes must reproduce the raw
+        // read exactly (every cell has exactly one contributor).
+        let averaged = context
+            .read_by_baseline_averaged(0, 0, 1, 1, None)
+            .expect("Failed to read_by_baseline_averaged");
+
+        assert_eq!(raw.len(), averaged.len());
+        for (r, a) in raw.iter().zip(averaged.iter()) {
+            assert!(approx_eq!(f32, *r, *a, F32Margin::default()));
+        }
+
+        // Averaging every pair of fine channels together halves the fine
+        // channel count but keeps the same number of baselines/pols.
+        let freq_avg = 2;
+        let coarsened = context
+            .read_by_baseline_averaged(0, 0, freq_avg, 1, None)
+            .expect("Failed to read_by_baseline_averaged");
+        assert_eq!(
+            coarsened.len(),
+            context.num_baselines
+                * (context.num_fine_channels_per_coarse / freq_avg)
+                * context.num_visibility_pols
+                * 2
+        );
+
+        // The very first averaged cell should be the mean of the first two
+        // raw fine channels for baseline 0, pol 0.
+        let cell_len = context.num_visibility_pols * 2;
+        let expected_re = (raw[0] as f64 + raw[cell_len] as f64) / 2.;
+        let expected_im = (raw[1] as f64 + raw[cell_len + 1] as f64) / 2.;
+        assert!(approx_eq!(
+            f64,
+            coarsened[0] as f64,
+            expected_re,
+            F64Margin::default()
+        ));
+        assert!(approx_eq!(
+            f64,
+            coarsened[1] as f64,
+            expected_im,
+            F64Margin::default()
+        ));
+    }
+
+    #[test]
+    fn test_write_uvfits_creates_expected_hdus() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        // This metafits has no RAPHASE/DECPHASE, which write_uvfits needs to
+        // compute UVW, so phase to the tile pointing.
+        context.ra_phase_center_degrees = Some(context.ra_tile_pointing_degrees);
+        context.dec_phase_center_degrees = Some(context.dec_tile_pointing_degrees);
+
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "mwalib_test_write_uvfits_{}.uvfits",
+            context.obsid
+        ));
+        // write_uvfits refuses to overwrite an existing file.
+        let _ = std::fs::remove_file(&path);
+
+        context
+            .write_uvfits(&path, 0..context.num_timesteps, 0..context.num_coarse_channels)
+            .expect("Failed to write_uvfits");
+
+        let mut fptr = FitsFile::open(&path).expect("Failed to reopen written uvfits file");
+        let uv_hdu = fptr.hdu("UV_DATA").expect("uvfits file is missing its UV_DATA HDU");
+        let baseline: Vec<f32> = uv_hdu
+            .read_col(&mut fptr, "BASELINE")
+            .expect("Failed to read BASELINE column");
+        assert_eq!(baseline.len(), context.num_timesteps * context.num_baselines);
+
+        let an_hdu = fptr
+            .hdu("AIPS AN")
+            .expect("uvfits file is missing its AIPS AN antenna table");
+
+        // ARRAYX/Y/Z must be the array centre's geocentric (ECEF) position,
+        // in metres (a few million metres in magnitude for an Earth-based
+        // array), not its geodetic latitude/longitude (which are radians,
+        // with magnitude at most a few).
+        let array_x: f64 = get_required_fits_key(&mut fptr, &an_hdu, "ARRAYX")
+            .expect("Failed to read ARRAYX key");
+        let array_y: f64 = get_required_fits_key(&mut fptr, &an_hdu, "ARRAYY")
+            .expect("Failed to read ARRAYY key");
+        let array_z: f64 = get_required_fits_key(&mut fptr, &an_hdu, "ARRAYZ")
+            .expect("Failed to read ARRAYZ key");
+        assert!(array_x.abs() > 1_000_000.);
+        assert!(array_y.abs() > 1_000_000.);
+        assert!(array_z.abs() > 1_000_000.);
+
+        std::fs::remove_file(&path).expect("Failed to clean up uvfits test file");
+    }
+
+    #[test]
+    fn test_mwaf_file_round_trip() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        // Flag every third fine channel of baseline 0, and nothing else.
+        let flags: Vec<Vec<Vec<bool>>> = (0..context.num_timesteps)
+            .map(|_| {
+                (0..context.num_baselines)
+                    .map(|baseline| {
+                        (0..context.num_fine_channels_per_coarse)
+                            .map(|fine_chan| baseline == 0 && fine_chan % 3 == 0)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "mwalib_test_mwaf_round_trip_{}.mwaf",
+            context.obsid
+        ));
+        // Remove any leftover file from a previous failed run; write_mwaf_file
+        // refuses to overwrite an existing file.
+        let _ = std::fs::remove_file(&path);
+
+        context
+            .write_mwaf_file(0, &flags, &path)
+            .expect("Failed to write_mwaf_file");
+
+        let read_back = context
+            .read_mwaf_file(&path)
+            .expect("Failed to read_mwaf_file");
+
+        std::fs::remove_file(&path).expect("Failed to clean up mwaf test file");
+
+        assert_eq!(read_back.obsid, context.obsid);
+        assert_eq!(read_back.num_baselines, context.num_baselines);
+        assert_eq!(read_back.num_channels, context.num_fine_channels_per_coarse);
+        assert_eq!(read_back.num_timesteps, context.num_timesteps);
+        assert_eq!(read_back.flags, flags);
+    }
+
+    #[test]
+    fn test_compute_smallest_unflagged_channel_band_trims_edges() {
+        let metafits_filename = "test_files/1101503312_1_timestep/1101503312.metafits";
+        let filename =
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits";
+
+        let metafits: String = String::from(metafits_filename);
+        let gpuboxfiles: Vec<String> = vec![String::from(filename)];
+        let mut context =
+            mwalibContext::new(&metafits, &gpuboxfiles).expect("Failed to create mwalibContext");
+
+        let (coarse_range, fine_range) = context.compute_smallest_unflagged_channel_band(None);
+
+        // With no supplied .mwaf flags, the coarse channel range is untouched...
+        assert_eq!(coarse_range, 0..context.num_coarse_channels);
+        // ...but the default edge/DC fine channels are always trimmed.
+        assert_eq!(fine_range.start, 1);
+        assert_eq!(fine_range.end, context.num_fine_channels_per_coarse - 1);
+        assert_eq!(context.default_channel_band, Some((coarse_range, fine_range)));
+    }
 }
@@ -2,69 +2,492 @@ use std::error::Error;
 use std::fmt;
 use std::io;
 use std::num;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-#[derive(Debug)]
+/// A type-erased, cheaply cloneable, thread-safe error source. `ErrorKind`
+/// stores its wrapped sources this way (rather than the concrete
+/// `io::Error`/`fitsio::errors::Error`/etc. types) so that `ErrorKind` itself
+/// can be `Clone` and `Send + Sync`, and so a single error value can be shared
+/// across worker threads reading gpubox files in parallel.
+pub type ArcError = Arc<dyn Error + Send + Sync + 'static>;
+
+/// Box and `Arc`-wrap any `'static + Send + Sync` error into an [`ArcError`].
+macro_rules! src_err_arc_wrap {
+    ($err:expr) => {
+        Arc::new($err) as ArcError
+    };
+}
+
+#[derive(Debug, Clone)]
 pub enum ErrorKind {
     Custom(String),
-    ParseInt(num::ParseIntError),
-    ParseFloat(num::ParseFloatError),
-    IO(io::Error),
-    Anyhow(anyhow::Error),
-    Fitsio(fitsio::errors::Error),
+    ParseInt(ArcError),
+    ParseFloat(ArcError),
+    IO(ArcError),
+    Anyhow(ArcError),
+    Fitsio(ArcError),
+    /// A FITS file could not be opened.
+    FitsOpen {
+        /// Path of the FITS file that failed to open.
+        fits_filename: PathBuf,
+        /// The underlying `fitsio` error.
+        source: ArcError,
+    },
+    /// A particular HDU of a FITS file could not be opened.
+    FitsHdu {
+        /// Path of the FITS file.
+        fits_filename: PathBuf,
+        /// The (zero-indexed) HDU number that was requested.
+        hdu_num: usize,
+        /// The underlying `fitsio` error.
+        source: ArcError,
+    },
+    /// A FITS keyword could not be read from a particular HDU of a FITS file.
+    FitsKeyword {
+        /// Path of the FITS file.
+        fits_filename: PathBuf,
+        /// The (zero-indexed) HDU number the keyword was read from.
+        hdu_num: usize,
+        /// The name of the offending FITS keyword.
+        keyword: String,
+        /// The underlying `fitsio` error.
+        source: ArcError,
+    },
+    /// A human-readable message describing the operation that failed,
+    /// optionally wrapping the lower-level error that caused it. Lets
+    /// higher-level code (e.g. metafits/gpubox parsing) attach context
+    /// without discarding the original `fitsio`/`io` error from `source()`.
+    Context {
+        /// What mwalib was trying to do when the error occurred.
+        message: String,
+        /// The underlying error, if any.
+        source: Option<ArcError>,
+    },
 }
 
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ErrorKind>();
+};
+
 impl From<num::ParseIntError> for ErrorKind {
     fn from(err: num::ParseIntError) -> ErrorKind {
-        ErrorKind::ParseInt(err)
+        ErrorKind::ParseInt(src_err_arc_wrap!(err))
     }
 }
 
 impl From<num::ParseFloatError> for ErrorKind {
     fn from(err: num::ParseFloatError) -> ErrorKind {
-        ErrorKind::ParseFloat(err)
+        ErrorKind::ParseFloat(src_err_arc_wrap!(err))
     }
 }
 
 impl From<io::Error> for ErrorKind {
     fn from(err: io::Error) -> ErrorKind {
-        ErrorKind::IO(err)
+        ErrorKind::IO(src_err_arc_wrap!(err))
     }
 }
 
 impl From<anyhow::Error> for ErrorKind {
     fn from(err: anyhow::Error) -> ErrorKind {
-        ErrorKind::Anyhow(err)
+        let boxed: Box<dyn Error + Send + Sync + 'static> = err.into();
+        ErrorKind::Anyhow(Arc::from(boxed))
     }
 }
 
 impl From<fitsio::errors::Error> for ErrorKind {
     fn from(err: fitsio::errors::Error) -> ErrorKind {
-        ErrorKind::Fitsio(err)
+        ErrorKind::Fitsio(src_err_arc_wrap!(err))
+    }
+}
+
+/// Stable, machine-readable error codes for the FFI boundary. Each
+/// `ErrorKind` variant maps to one of these via [`ErrorKind::code`], so C/
+/// Python callers can branch on failure categories instead of matching on
+/// the `Display` string. Discriminants are part of mwalib's FFI ABI: existing
+/// ones must not change or be reused across releases, only appended to.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// No error occurred.
+    Ok = 0,
+    Custom = 1,
+    ParseInt = 2,
+    ParseFloat = 3,
+    Io = 4,
+    Fitsio = 5,
+    FileNotFound = 6,
+    KeywordMissing = 7,
+    HduMissing = 8,
+    /// A required context/struct pointer argument was null.
+    NullContext = 9,
+    /// A required output pointer argument was null.
+    NullOutPointer = 10,
+    /// A caller-supplied buffer was too small to hold the result.
+    BufferTooSmall = 11,
+    /// The requested operation had no data to return.
+    NoData = 12,
+    /// An internal error occurred that doesn't fit any other category (e.g. a
+    /// caught panic at the FFI boundary).
+    InternalError = 13,
+}
+
+impl ErrorKind {
+    /// Map this error to its stable [`ErrorCode`] for FFI callers.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ErrorKind::Custom(_) => ErrorCode::Custom,
+            ErrorKind::ParseInt(_) => ErrorCode::ParseInt,
+            ErrorKind::ParseFloat(_) => ErrorCode::ParseFloat,
+            ErrorKind::IO(_) => ErrorCode::Io,
+            ErrorKind::Anyhow(_) => ErrorCode::Custom,
+            ErrorKind::Fitsio(_) => ErrorCode::Fitsio,
+            ErrorKind::FitsOpen { .. } => ErrorCode::FileNotFound,
+            ErrorKind::FitsHdu { .. } => ErrorCode::HduMissing,
+            ErrorKind::FitsKeyword { .. } => ErrorCode::KeywordMissing,
+            ErrorKind::Context { .. } => ErrorCode::Custom,
+        }
+    }
+}
+
+/// Implemented by mwalib's error types so FFI entry points can convert any of
+/// them to a stable [`ErrorCode`] via a single generic helper
+/// (`result_to_error_code` in the `ffi` module), regardless of which error
+/// type a particular `Result` uses.
+pub trait ToErrorCode {
+    /// This error's stable [`ErrorCode`] for FFI callers.
+    fn to_error_code(&self) -> ErrorCode;
+}
+
+impl ToErrorCode for ErrorKind {
+    fn to_error_code(&self) -> ErrorCode {
+        self.code()
+    }
+}
+
+impl ToErrorCode for MwalibError {
+    fn to_error_code(&self) -> ErrorCode {
+        match self {
+            MwalibError::MetafitsOpen { source, .. } => source.code(),
+            MwalibError::InvalidFitsKeyword { source, .. } => source.code(),
+            MwalibError::InconsistentGpuboxSet { .. }
+            | MwalibError::VoltageFileMismatch { .. }
+            | MwalibError::UnknownCorrelatorVersion { .. }
+            | MwalibError::InvalidAntennaSelection { .. } => ErrorCode::Custom,
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Construct a [`ErrorKind::FitsOpen`] for a failure to open `fits_filename`.
+    pub fn fits_open(fits_filename: PathBuf, source: fitsio::errors::Error) -> ErrorKind {
+        ErrorKind::FitsOpen {
+            fits_filename,
+            source: src_err_arc_wrap!(source),
+        }
+    }
+
+    /// Construct a [`ErrorKind::FitsHdu`] for a failure to open HDU `hdu_num`
+    /// of `fits_filename`.
+    pub fn fits_hdu(
+        fits_filename: PathBuf,
+        hdu_num: usize,
+        source: fitsio::errors::Error,
+    ) -> ErrorKind {
+        ErrorKind::FitsHdu {
+            fits_filename,
+            hdu_num,
+            source: src_err_arc_wrap!(source),
+        }
+    }
+
+    /// Construct a [`ErrorKind::FitsKeyword`] for a failure to read `keyword`
+    /// from HDU `hdu_num` of `fits_filename`.
+    pub fn fits_keyword(
+        fits_filename: PathBuf,
+        hdu_num: usize,
+        keyword: String,
+        source: fitsio::errors::Error,
+    ) -> ErrorKind {
+        ErrorKind::FitsKeyword {
+            fits_filename,
+            hdu_num,
+            keyword,
+            source: src_err_arc_wrap!(source),
+        }
     }
 }
 
 impl Error for ErrorKind {
-    fn description(&self) -> &str {
-        match *self {
-            ErrorKind::Custom(ref err) => err,
-            ErrorKind::ParseInt(ref err) => err.description(),
-            ErrorKind::ParseFloat(ref err) => err.description(),
-            ErrorKind::IO(ref err) => err.description(),
-            ErrorKind::Anyhow(ref err) => err.description(),
-            ErrorKind::Fitsio(ref err) => err.description(),
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ErrorKind::Custom(_) => None,
+            ErrorKind::ParseInt(err) => Some(&**err),
+            ErrorKind::ParseFloat(err) => Some(&**err),
+            ErrorKind::IO(err) => Some(&**err),
+            ErrorKind::Anyhow(err) => Some(&**err),
+            ErrorKind::Fitsio(err) => Some(&**err),
+            ErrorKind::FitsOpen { source, .. } => Some(&**source),
+            ErrorKind::FitsHdu { source, .. } => Some(&**source),
+            ErrorKind::FitsKeyword { source, .. } => Some(&**source),
+            ErrorKind::Context { source, .. } => {
+                source.as_ref().map(|err| &**err as &(dyn Error + 'static))
+            }
         }
     }
 }
 
 impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            ErrorKind::Custom(ref err) => err.fmt(f),
-            ErrorKind::ParseInt(ref err) => err.fmt(f),
-            ErrorKind::ParseFloat(ref err) => err.fmt(f),
-            ErrorKind::IO(ref err) => err.fmt(f),
-            ErrorKind::Anyhow(ref err) => err.fmt(f),
-            ErrorKind::Fitsio(ref err) => err.fmt(f),
+        match self {
+            ErrorKind::Custom(err) => err.fmt(f),
+            ErrorKind::ParseInt(err) => err.fmt(f),
+            ErrorKind::ParseFloat(err) => err.fmt(f),
+            ErrorKind::IO(err) => err.fmt(f),
+            ErrorKind::Anyhow(err) => err.fmt(f),
+            ErrorKind::Fitsio(err) => err.fmt(f),
+            ErrorKind::FitsOpen {
+                fits_filename,
+                source,
+            } => write!(f, "Couldn't open '{}': {}", fits_filename.display(), source),
+            ErrorKind::FitsHdu {
+                fits_filename,
+                hdu_num,
+                source,
+            } => write!(
+                f,
+                "Couldn't open HDU {} of '{}': {}",
+                hdu_num,
+                fits_filename.display(),
+                source
+            ),
+            ErrorKind::FitsKeyword {
+                fits_filename,
+                hdu_num,
+                keyword,
+                source,
+            } => write!(
+                f,
+                "Couldn't read keyword '{}' in HDU {} of '{}': {}",
+                keyword,
+                hdu_num,
+                fits_filename.display(),
+                source
+            ),
+            ErrorKind::Context { message, .. } => message.fmt(f),
+        }
+    }
+}
+
+/// Pretty-prints an error's full `source()` chain, one indented line per
+/// link, e.g.:
+///
+/// ```text
+/// 0: could not open gpubox file
+///   1: fitsio: HDU not found
+///     2: No such file or directory
+/// ```
+///
+/// Radio-telescope data errors often nest three or four layers deep
+/// (operation -> FITS call -> libcfitsio status -> OS errno); a flat
+/// top-level `Display` hides where the failure actually occurred. Stops at
+/// the first link with no further `source()`.
+pub struct ErrorChainDisplay<'a>(pub &'a (dyn Error + 'static));
+
+impl<'a> fmt::Display for ErrorChainDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut depth = 0;
+        let mut current: Option<&(dyn Error + 'static)> = Some(self.0);
+
+        while let Some(err) = current {
+            writeln!(f, "{}{}: {}", "  ".repeat(depth), depth, err)?;
+            current = err.source();
+            depth += 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl ErrorKind {
+    /// See [`ErrorChainDisplay`].
+    pub fn display_chain(&self) -> ErrorChainDisplay {
+        ErrorChainDisplay(self)
+    }
+
+    /// Walk this error's cause chain (starting with `self`) and return the
+    /// first link that downcasts to `T`, e.g. to recover an `io::Error`
+    /// buried inside a wrapped `Anyhow` or `Fitsio` error.
+    pub fn downcast_source_ref<T: Error + 'static>(&self) -> Option<&T> {
+        let mut current: Option<&(dyn Error + 'static)> = Some(self);
+        while let Some(err) = current {
+            if let Some(t) = err.downcast_ref::<T>() {
+                return Some(t);
+            }
+            current = err.source();
+        }
+        None
+    }
+
+    /// Whether this error's cause chain contains an [`io::Error`] of kind
+    /// [`io::ErrorKind::NotFound`] — e.g. a genuinely missing gpubox or
+    /// metafits file, as opposed to one that exists but is corrupt.
+    pub fn is_file_not_found(&self) -> bool {
+        self.downcast_source_ref::<io::Error>()
+            .map(|e| e.kind() == io::ErrorKind::NotFound)
+            .unwrap_or(false)
+    }
+}
+
+/// A structured error covering every stage of mwalib's metafits/gpubox/voltage
+/// file parsing. Each variant carries the offending filename (and, where
+/// relevant, the specific field) so callers can show stage-specific messages
+/// or recover programmatically, rather than pattern-matching on a formatted
+/// string.
+#[derive(Debug)]
+pub enum MwalibError {
+    /// The metafits file could not be opened.
+    MetafitsOpen {
+        /// Path of the metafits file that failed to open.
+        filename: String,
+        /// The underlying error.
+        source: ErrorKind,
+    },
+    /// A required FITS keyword was missing from a file, or its value could
+    /// not be parsed into the expected type.
+    InvalidFitsKeyword {
+        /// Path of the FITS file containing (or missing) the keyword.
+        filename: String,
+        /// The name of the offending FITS keyword.
+        keyword: String,
+        /// The underlying error.
+        source: ErrorKind,
+    },
+    /// The set of gpubox files for a coarse channel/timestep was
+    /// inconsistent (missing, duplicated, or a mismatched HDU count).
+    InconsistentGpuboxSet {
+        /// Coarse channel index the inconsistency was found at.
+        coarse_chan_index: usize,
+        /// Timestep index the inconsistency was found at.
+        timestep_index: usize,
+        /// Description of the inconsistency.
+        detail: String,
+    },
+    /// A voltage file did not match what was expected (a missing/duplicated
+    /// batch, or a file size mismatch).
+    VoltageFileMismatch {
+        /// The voltage filename at fault.
+        filename: String,
+        /// Description of the mismatch.
+        detail: String,
+    },
+    /// The `CorrelatorVersion` could not be determined from the supplied
+    /// gpubox file's name or contents.
+    UnknownCorrelatorVersion {
+        /// The gpubox filename that was inspected.
+        filename: String,
+    },
+    /// An antenna (tile) selection (e.g. `MetafitsContext::with_antenna_selection`)
+    /// contained an index that was out of range, or duplicated.
+    InvalidAntennaSelection {
+        /// The offending antenna index.
+        index: usize,
+        /// The number of antennas available to select from.
+        num_ants: usize,
+    },
+}
+
+impl From<ErrorKind> for MwalibError {
+    /// Generic FITS/IO errors (e.g. raised inside the `fits_open!`/
+    /// `get_required_fits_key!` macros before a filename/keyword is known to
+    /// the caller) are surfaced as a [`MwalibError::MetafitsOpen`] with an
+    /// empty filename; callers with more context should construct the
+    /// specific variant directly instead of relying on this conversion.
+    fn from(err: ErrorKind) -> MwalibError {
+        MwalibError::MetafitsOpen {
+            filename: String::new(),
+            source: err,
+        }
+    }
+}
+
+impl Error for MwalibError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MwalibError::MetafitsOpen { source, .. } => Some(source),
+            MwalibError::InvalidFitsKeyword { source, .. } => Some(source),
+            MwalibError::InconsistentGpuboxSet { .. }
+            | MwalibError::VoltageFileMismatch { .. }
+            | MwalibError::UnknownCorrelatorVersion { .. }
+            | MwalibError::InvalidAntennaSelection { .. } => None,
+        }
+    }
+}
+
+impl MwalibError {
+    /// See [`ErrorChainDisplay`].
+    pub fn display_chain(&self) -> ErrorChainDisplay {
+        ErrorChainDisplay(self)
+    }
+
+    /// See [`ErrorKind::downcast_source_ref`].
+    pub fn downcast_source_ref<T: Error + 'static>(&self) -> Option<&T> {
+        let mut current: Option<&(dyn Error + 'static)> = Some(self);
+        while let Some(err) = current {
+            if let Some(t) = err.downcast_ref::<T>() {
+                return Some(t);
+            }
+            current = err.source();
+        }
+        None
+    }
+
+    /// See [`ErrorKind::is_file_not_found`].
+    pub fn is_file_not_found(&self) -> bool {
+        self.downcast_source_ref::<io::Error>()
+            .map(|e| e.kind() == io::ErrorKind::NotFound)
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for MwalibError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MwalibError::MetafitsOpen { filename, source } => {
+                write!(f, "{}: could not open metafits file: {}", filename, source)
+            }
+            MwalibError::InvalidFitsKeyword {
+                filename,
+                keyword,
+                source,
+            } => write!(
+                f,
+                "{}: missing or invalid '{}' keyword: {}",
+                filename, keyword, source
+            ),
+            MwalibError::InconsistentGpuboxSet {
+                coarse_chan_index,
+                timestep_index,
+                detail,
+            } => write!(
+                f,
+                "inconsistent gpubox file set at coarse channel {}, timestep {}: {}",
+                coarse_chan_index, timestep_index, detail
+            ),
+            MwalibError::VoltageFileMismatch { filename, detail } => {
+                write!(f, "{}: voltage file mismatch: {}", filename, detail)
+            }
+            MwalibError::UnknownCorrelatorVersion { filename } => write!(
+                f,
+                "{}: could not determine correlator version",
+                filename
+            ),
+            MwalibError::InvalidAntennaSelection { index, num_ants } => write!(
+                f,
+                "antenna index {} is out of range or duplicated (0..{} available)",
+                index, num_ants
+            ),
         }
     }
 }
\ No newline at end of file
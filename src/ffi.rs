@@ -14,11 +14,52 @@ C).
 
 use crate::*;
 extern crate chrono;
+use chrono::{DateTime, Duration};
 use libc::{c_char, c_float, c_longlong, size_t, time_t};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::ptr;
 use std::slice;
 
+thread_local! {
+    /// The full, untruncated text of the most recent [`set_error_message`]
+    /// call on this thread, retrievable via `mwalibContext_get_last_error_message`
+    /// or `mwalib_get_last_error` regardless of how small (or null) the
+    /// failing call's own `error_message` buffer was — the errno/`strerror_r`
+    /// convention, rather than forcing every caller to pre-allocate a
+    /// worst-case-sized buffer up front.
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Stable, machine-readable status codes returned by every `mwalib*` FFI entry
+/// point that can fail. The human-readable detail is still written into the
+/// caller-supplied `error_message` buffer (as before); this enum just lets C/Python
+/// callers branch on *what* went wrong without parsing that string.
+///
+/// New variants may be added in future versions; callers should not assume this
+/// list is exhaustive and should always fall back to displaying `error_message`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MwalibErrorCode {
+    /// The call completed successfully.
+    Success = 0,
+    /// A required pointer argument was NULL.
+    NullPointer = 1,
+    /// An index (timestep/antenna/coarse channel/rf_input) was out of range.
+    InvalidIndex = 2,
+    /// A buffer provided by the caller was too small to hold the result.
+    BufferTooSmall = 3,
+    /// Reading an HDU out of a gpubox/mwax FITS file failed.
+    CorrelatorReadError = 4,
+    /// No data was returned from an otherwise successful read.
+    NoDataReturned = 5,
+    /// Any other failure; see `error_message` for detail.
+    Other = 99,
+}
+
 /// Generic helper function for all FFI modules to take an already allocated C string
 /// and update it with an error message. This is used to pass error messages back to C from Rust.
 ///
@@ -42,6 +83,13 @@ use std::slice;
 /// - Free `error_buffer_ptr` once finished with the buffer
 ///
 fn set_error_message(in_message: &str, error_buffer_ptr: *mut u8, error_buffer_len: size_t) {
+    // Stash the full message for `mwalibContext_get_last_error_message`/
+    // `mwalib_get_last_error`, regardless of whether `error_buffer_ptr` below
+    // is usable.
+    if let Ok(full_message) = CString::new(in_message) {
+        LAST_ERROR.with(|cell| *cell.borrow_mut() = Some(full_message));
+    }
+
     // Don't do anything if the pointer is null.
     if error_buffer_ptr.is_null() {
         return;
@@ -73,6 +121,84 @@ fn set_error_message(in_message: &str, error_buffer_ptr: *mut u8, error_buffer_l
     }
 }
 
+/// Turn a caught panic payload (from [`std::panic::catch_unwind`]) into a
+/// human-readable message, for inclusion in a caller's error buffer.
+///
+/// A Rust panic unwinding across an `extern "C"` boundary is undefined
+/// behaviour, so every `mwalib*` FFI entry point below catches panics at its
+/// own boundary rather than letting them propagate into the caller's code.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Return the full text of the most recent error message set on this thread
+/// (via any `mwalib*` FFI call that failed), copying up to `buffer_len - 1`
+/// bytes plus a NUL terminator into `buffer`. The *real* message length
+/// (excluding the NUL terminator) is always returned, even if `buffer` was too
+/// small (or null) to hold it, so a binding can tell it was truncated and
+/// retry with a bigger buffer — the same convention as POSIX `strerror_r`,
+/// rather than every caller having to pre-allocate a worst-case-sized error
+/// buffer for each individual call.
+///
+/// # Returns
+///
+/// * The length, in bytes, of the full last error message (0 if none has been set yet).
+///
+/// # Safety
+/// * `buffer` must point to at least `buffer_len` allocated bytes, or be
+///   null (in which case nothing is copied, but the real length is still returned).
+#[no_mangle]
+pub unsafe extern "C" fn mwalibContext_get_last_error_message(
+    buffer: *mut u8,
+    buffer_len: size_t,
+) -> size_t {
+    LAST_ERROR.with(|cell| {
+        let borrowed = cell.borrow();
+        let message = match borrowed.as_ref() {
+            Some(m) => m,
+            None => return 0,
+        };
+        let message_bytes = message.as_bytes();
+
+        if !buffer.is_null() && buffer_len > 0 {
+            let copy_len = message_bytes.len().min(buffer_len - 1);
+            let out = slice::from_raw_parts_mut(buffer, buffer_len);
+            out[..copy_len].copy_from_slice(&message_bytes[..copy_len]);
+            out[copy_len] = 0;
+        }
+
+        message_bytes.len()
+    })
+}
+
+/// Return a pointer to the NUL-terminated text of the most recent error
+/// message set on this thread, without requiring the caller to supply (or
+/// size) a buffer up front — the `errno`/`strerror` convention, as opposed to
+/// [`mwalibContext_get_last_error_message`]'s `strerror_r`-style copy-into-buffer
+/// form. Returns a null pointer if no error has been set yet on this thread.
+///
+/// # Returns
+///
+/// * A pointer to the last error message, or null if none has been set.
+///
+/// # Safety
+/// * The returned pointer is only valid until the next `mwalib*` FFI call on
+///   this thread (any call that fails will overwrite it); callers that need
+///   to retain the message must copy it out before making another call.
+#[no_mangle]
+pub unsafe extern "C" fn mwalib_get_last_error() -> *const c_char {
+    LAST_ERROR.with(|cell| match cell.borrow().as_ref() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
 /// Free a rust-allocated CString.
 ///
 /// mwalib uses error strings to detail the caller with anything that went
@@ -93,11 +219,14 @@ fn set_error_message(in_message: &str, error_buffer_ptr: *mut u8, error_buffer_l
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
 pub unsafe extern "C" fn mwalib_free_rust_cstring(rust_cstring: *mut c_char) {
-    // Don't do anything if the pointer is null.
-    if rust_cstring.is_null() {
-        return;
-    }
-    CString::from_raw(rust_cstring);
+    // Catch any panic here so it can't unwind across the FFI boundary.
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        // Don't do anything if the pointer is null.
+        if rust_cstring.is_null() {
+            return;
+        }
+        CString::from_raw(rust_cstring);
+    }));
 }
 
 /// Create and return a pointer to an `mwalibContext` struct
@@ -131,21 +260,54 @@ pub unsafe extern "C" fn mwalibContext_get(
     error_message: *mut u8,
     error_message_length: size_t,
 ) -> *mut mwalibContext {
-    let m = CStr::from_ptr(metafits).to_str().unwrap().to_string();
-    let gpubox_slice = slice::from_raw_parts(gpuboxes, gpubox_count);
-    let mut gpubox_files = Vec::with_capacity(gpubox_count);
-    for g in gpubox_slice {
-        let s = CStr::from_ptr(*g).to_str().unwrap();
-        gpubox_files.push(s.to_string())
-    }
-    let context = match mwalibContext::new(&m, &gpubox_files) {
-        Ok(c) => c,
-        Err(e) => {
-            set_error_message(&format!("{}", e), error_message, error_message_length);
-            return ptr::null_mut();
+    catch_unwind(AssertUnwindSafe(|| {
+        let m = match CStr::from_ptr(metafits).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => {
+                set_error_message(
+                    "mwalibContext_get() ERROR: metafits is not valid UTF-8",
+                    error_message,
+                    error_message_length,
+                );
+                return ptr::null_mut();
+            }
+        };
+        let gpubox_slice = slice::from_raw_parts(gpuboxes, gpubox_count);
+        let mut gpubox_files = Vec::with_capacity(gpubox_count);
+        for g in gpubox_slice {
+            let s = match CStr::from_ptr(*g).to_str() {
+                Ok(s) => s,
+                Err(_) => {
+                    set_error_message(
+                        "mwalibContext_get() ERROR: a gpubox filename is not valid UTF-8",
+                        error_message,
+                        error_message_length,
+                    );
+                    return ptr::null_mut();
+                }
+            };
+            gpubox_files.push(s.to_string())
         }
-    };
-    Box::into_raw(Box::new(context))
+        let context = match mwalibContext::new(&m, &gpubox_files) {
+            Ok(c) => c,
+            Err(e) => {
+                set_error_message(&format!("{}", e), error_message, error_message_length);
+                return ptr::null_mut();
+            }
+        };
+        Box::into_raw(Box::new(context))
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibContext_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
 }
 
 /// Free a previously-allocated `mwalibContext` struct.
@@ -167,10 +329,180 @@ pub unsafe extern "C" fn mwalibContext_get(
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
 pub unsafe extern "C" fn mwalibContext_free(context_ptr: *mut mwalibContext) {
-    if context_ptr.is_null() {
-        return;
-    }
-    Box::from_raw(context_ptr);
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() {
+            return;
+        }
+        Box::from_raw(context_ptr);
+    }));
+}
+
+/// Create and return a pointer to a fully-populated, synthetic `mwalibContext`
+/// built entirely from the given dimensions, with no metafits/gpubox file I/O.
+///
+/// Tile ids are assigned sequentially from 0, antenna/rf_input names are
+/// `Tile{:03}` with alternating `X`/`Y` polarisations, timesteps are evenly
+/// spaced one second apart starting at the UNIX epoch, and coarse channels
+/// are sequential 1.28 MHz channels starting at receiver channel 1. Every
+/// existing accessor (`mwalibMetadata_get`, `mwalibRFInput_get`,
+/// `mwalibCoarseChannel_get`, `mwalibAntenna_get`, `mwalibTimeStep_get`, and
+/// their `_get_all` bulk counterparts) works against the result unchanged,
+/// so downstream bindings can unit-test their `mwalibContext`-consuming code
+/// without shipping the large real test fixtures under `test_files/`.
+///
+/// # Arguments
+///
+/// * `n_antennas` - number of antennas (tiles) to synthesise. rf_inputs will be `2 * n_antennas` (X and Y).
+///
+/// * `n_timesteps` - number of timesteps to synthesise.
+///
+/// * `n_coarse_channels` - number of coarse channels to synthesise.
+///
+///
+/// # Returns
+///
+/// * A Rust-owned, fully-populated `mwalibContext` struct.
+///
+///
+/// # Safety
+/// * Caller *must* call `mwalibContext_free` to release the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibContext_get_mock(
+    n_antennas: size_t,
+    n_timesteps: size_t,
+    n_coarse_channels: size_t,
+) -> *mut mwalibContext {
+    catch_unwind(AssertUnwindSafe(|| {
+        let epoch = DateTime::parse_from_rfc3339("1970-01-01T00:00:00+00:00").unwrap();
+
+        let mut antennas = Vec::with_capacity(n_antennas);
+        let mut rf_inputs = Vec::with_capacity(n_antennas * 2);
+        for antenna_index in 0..n_antennas {
+            let tile_id = antenna_index as u32;
+            let tile_name = format!("Tile{:03}", tile_id);
+            antennas.push(mwalibAntenna {
+                antenna: antenna_index as u32,
+                tile_id,
+                tile_name: CString::new(tile_name.clone()).unwrap().into_raw(),
+            });
+            for (pol_index, pol) in ["X", "Y"].iter().enumerate() {
+                rf_inputs.push(mwalibRFInput {
+                    input: (antenna_index * 2 + pol_index) as u32,
+                    antenna: antenna_index as u32,
+                    tile_id,
+                    tile_name: CString::new(tile_name.clone()).unwrap().into_raw(),
+                    pol: CString::new(*pol).unwrap().into_raw(),
+                    electrical_length_m: 0.,
+                    north_m: 0.,
+                    east_m: 0.,
+                    height_m: 0.,
+                    vcs_order: (antenna_index * 2 + pol_index) as u32,
+                    subfile_order: (antenna_index * 2 + pol_index) as u32,
+                    flagged: false,
+                });
+            }
+        }
+
+        let mut timesteps = Vec::with_capacity(n_timesteps);
+        for timestep_index in 0..n_timesteps {
+            timesteps.push(mwalibTimeStep {
+                unix_time_ms: timestep_index as u64 * 1000,
+            });
+        }
+
+        let mut coarse_channels = Vec::with_capacity(n_coarse_channels);
+        for coarse_channel_index in 0..n_coarse_channels {
+            let receiver_channel_number = coarse_channel_index + 1;
+            let channel_width_hz = 1_280_000;
+            let channel_start_hz = receiver_channel_number as u32 * channel_width_hz;
+            coarse_channels.push(mwalibCoarseChannel {
+                correlator_channel_number: coarse_channel_index,
+                receiver_channel_number,
+                gpubox_number: coarse_channel_index + 1,
+                channel_width_hz,
+                channel_start_hz,
+                channel_centre_hz: channel_start_hz + channel_width_hz / 2,
+                channel_end_hz: channel_start_hz + channel_width_hz,
+            });
+        }
+
+        let context = mwalibContext {
+            mwa_latitude_radians: 0.,
+            mwa_longitude_radians: 0.,
+            mwa_altitude_metres: 0.,
+            coax_v_factor: 0.,
+            obsid: 0,
+            scheduled_start_gpstime_milliseconds: 0,
+            scheduled_end_gpstime_milliseconds: 0,
+            scheduled_start_unix_time_milliseconds: 0,
+            scheduled_end_unix_time_milliseconds: n_timesteps as u64 * 1000,
+            scheduled_start_utc: epoch,
+            scheduled_end_utc: epoch + Duration::milliseconds(n_timesteps as i64 * 1000),
+            scheduled_start_mjd: 0.,
+            scheduled_end_mjd: 0.,
+            scheduled_duration_milliseconds: n_timesteps as u64 * 1000,
+            ra_tile_pointing_degrees: 0.,
+            dec_tile_pointing_degrees: 0.,
+            ra_phase_center_degrees: None,
+            dec_phase_center_degrees: None,
+            azimuth_degrees: 0.,
+            altitude_degrees: 0.,
+            sun_altitude_degrees: 0.,
+            sun_distance_degrees: 0.,
+            moon_distance_degrees: 0.,
+            jupiter_distance_degrees: 0.,
+            lst_degrees: 0.,
+            hour_angle_string: String::new(),
+            grid_name: String::new(),
+            grid_number: 0,
+            creator: String::new(),
+            project_id: String::new(),
+            observation_name: "mwalibContext_get_mock".to_string(),
+            mode: String::new(),
+            receivers: Vec::new(),
+            delays: Vec::new(),
+            global_analogue_attenuation_db: 0.,
+            quack_time_duration_milliseconds: 0,
+            good_time_unix_milliseconds: 0,
+            corr_version: CorrelatorVersion::V2,
+            start_unix_time_milliseconds: 0,
+            end_unix_time_milliseconds: n_timesteps.saturating_sub(1) as u64 * 1000,
+            duration_milliseconds: n_timesteps as u64 * 1000,
+            num_timesteps: n_timesteps,
+            timesteps,
+            num_antennas: n_antennas,
+            antennas,
+            num_baselines: (n_antennas * (n_antennas + 1)) / 2,
+            num_rf_inputs: n_antennas * 2,
+            rf_inputs,
+            num_antenna_pols: 2,
+            num_visibility_pols: 4,
+            num_coarse_channels: n_coarse_channels,
+            coarse_channels,
+            integration_time_milliseconds: 1000,
+            fine_channel_width_hz: 10_000,
+            observation_bandwidth_hz: n_coarse_channels as u32 * 1_280_000,
+            coarse_channel_width_hz: 1_280_000,
+            num_fine_channels_per_coarse: 128,
+            metafits_filename: "mwalibContext_get_mock".to_string(),
+            gpubox_batches: Vec::new(),
+            gpubox_time_map: BTreeMap::new(),
+            num_timestep_coarse_channel_bytes: 0,
+            num_timestep_coarse_channel_floats: 0,
+            num_gpubox_files: 0,
+            legacy_conversion_table: Vec::new(),
+            cable_length_correction_enabled: false,
+            geometric_correction_enabled: false,
+            digital_gain_correction_enabled: false,
+            passband_correction_enabled: false,
+            applied_corrections: HashMap::new(),
+            default_channel_band: None,
+            baseline_conjugation: BaselineConjugation::Ant1Ant2,
+        };
+
+        Box::into_raw(Box::new(context))
+    }))
+    .unwrap_or(ptr::null_mut())
 }
 
 /// Display an `mwalibContext` struct.
@@ -187,7 +519,8 @@ pub unsafe extern "C" fn mwalibContext_free(context_ptr: *mut mwalibContext) {
 ///
 /// # Returns
 ///
-/// * 0 on success, 1 on failure
+/// * `MwalibErrorCode::Success` on success, or another `MwalibErrorCode` variant on failure
+///   (check `error_message` for detail)
 ///
 ///
 /// # Safety
@@ -198,18 +531,31 @@ pub unsafe extern "C" fn mwalibContext_display(
     context_ptr: *const mwalibContext,
     error_message: *mut u8,
     error_message_length: size_t,
-) -> i32 {
-    if context_ptr.is_null() {
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_display() ERROR: null pointer passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        }
+        let context = &*context_ptr;
+        println!("{}", context);
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
         set_error_message(
-            "mwalibContext_display() ERROR: null pointer passed in",
+            &format!(
+                "mwalibContext_display() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
             error_message,
             error_message_length,
         );
-        return 1;
-    }
-    let context = &*context_ptr;
-    println!("{}", context);
-    0
+        MwalibErrorCode::Other
+    })
 }
 
 /// Read a single timestep / coarse channel of MWA data.
@@ -238,7 +584,8 @@ pub unsafe extern "C" fn mwalibContext_display(
 ///
 /// # Returns
 ///
-/// * 0 on success, 1 on failure
+/// * `MwalibErrorCode::Success` on success, or another `MwalibErrorCode` variant on failure
+///   (check `error_message` for detail)
 ///
 ///
 /// # Safety
@@ -254,50 +601,72 @@ pub unsafe extern "C" fn mwalibContext_read_by_baseline(
     buffer_len: size_t,
     error_message: *mut u8,
     error_message_length: size_t,
-) -> i32 {
-    // Load the previously-initialised context and buffer structs. Exit if
-    // either of these are null.
-    let context = if context_ptr.is_null() {
-        set_error_message(
-            "mwalibContext_read_by_baseline() ERROR: null pointer for context_ptr passed in",
-            error_message,
-            error_message_length,
-        );
-        return 1;
-    } else {
-        &mut *context_ptr
-    };
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        // Load the previously-initialised context and buffer structs. Exit if
+        // either of these are null.
+        let context = if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_read_by_baseline() ERROR: null pointer for context_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        } else {
+            &mut *context_ptr
+        };
 
-    // Don't do anything if the buffer pointer is null.
-    if buffer_ptr.is_null() {
-        return 1;
-    }
+        // Don't do anything if the buffer pointer is null.
+        if buffer_ptr.is_null() {
+            return MwalibErrorCode::NullPointer;
+        }
+
+        let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
 
-    let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
+        // Read data in.
+        let data = match context.read_by_baseline(timestep_index, coarse_channel_index) {
+            Ok(data) => data,
+            Err(e) => {
+                set_error_message(&format!("{}", e), error_message, error_message_length);
+                return MwalibErrorCode::CorrelatorReadError;
+            }
+        };
 
-    // Read data in.
-    let data = match context.read_by_baseline(timestep_index, coarse_channel_index) {
-        Ok(data) => data,
-        Err(e) => {
-            set_error_message(&format!("{}", e), error_message, error_message_length);
-            return 1;
+        // If the data buffer is empty, report NoDataReturned.
+        if data.is_empty() {
+            set_error_message(
+                "mwalibContext_read_by_baseline() ERROR: no data was returned.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NoDataReturned;
+        }
+
+        if data.len() > output_slice.len() {
+            set_error_message(
+                "mwalibContext_read_by_baseline() ERROR: caller-supplied buffer is too small.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::BufferTooSmall;
         }
-    };
 
-    // If the data buffer is empty, then just return a null pointer.
-    if data.is_empty() {
+        // Populate the buffer which was provided to us by caller
+        output_slice[..data.len()].copy_from_slice(data.as_slice());
+        // Return Success
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
         set_error_message(
-            "mwalibContext_read_by_baseline() ERROR: no data was returned.",
+            &format!(
+                "mwalibContext_read_by_baseline() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
             error_message,
             error_message_length,
         );
-        return 1;
-    }
-
-    // Populate the buffer which was provided to us by caller
-    output_slice[..data.len()].copy_from_slice(data.as_slice());
-    // Return Success
-    0
+        MwalibErrorCode::Other
+    })
 }
 
 /// Read a single timestep / coarse channel of MWA data.
@@ -326,7 +695,8 @@ pub unsafe extern "C" fn mwalibContext_read_by_baseline(
 ///
 /// # Returns
 ///
-/// * 0 on success, 1 on failure
+/// * `MwalibErrorCode::Success` on success, or another `MwalibErrorCode` variant on failure
+///   (check `error_message` for detail)
 ///
 ///
 /// # Safety
@@ -342,50 +712,72 @@ pub unsafe extern "C" fn mwalibContext_read_by_frequency(
     buffer_len: size_t,
     error_message: *mut u8,
     error_message_length: size_t,
-) -> i32 {
-    // Load the previously-initialised context and buffer structs. Exit if
-    // either of these are null.
-    let context = if context_ptr.is_null() {
-        set_error_message(
-            "mwalibContext_read_by_frequency() ERROR: null pointer for context_ptr passed in",
-            error_message,
-            error_message_length,
-        );
-        return 1;
-    } else {
-        &mut *context_ptr
-    };
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        // Load the previously-initialised context and buffer structs. Exit if
+        // either of these are null.
+        let context = if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_read_by_frequency() ERROR: null pointer for context_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        } else {
+            &mut *context_ptr
+        };
 
-    // Don't do anything if the buffer pointer is null.
-    if buffer_ptr.is_null() {
-        return 1;
-    }
+        // Don't do anything if the buffer pointer is null.
+        if buffer_ptr.is_null() {
+            return MwalibErrorCode::NullPointer;
+        }
 
-    let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
+        let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
 
-    // Read data in.
-    let data = match context.read_by_frequency(timestep_index, coarse_channel_index) {
-        Ok(data) => data,
-        Err(e) => {
-            set_error_message(&format!("{}", e), error_message, error_message_length);
-            return 1;
+        // Read data in.
+        let data = match context.read_by_frequency(timestep_index, coarse_channel_index) {
+            Ok(data) => data,
+            Err(e) => {
+                set_error_message(&format!("{}", e), error_message, error_message_length);
+                return MwalibErrorCode::CorrelatorReadError;
+            }
+        };
+
+        // If the data buffer is empty, report NoDataReturned.
+        if data.is_empty() {
+            set_error_message(
+                "mwalibContext_read_by_frequency() ERROR: no data was returned.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NoDataReturned;
         }
-    };
 
-    // If the data buffer is empty, then just return a null pointer.
-    if data.is_empty() {
+        if data.len() > output_slice.len() {
+            set_error_message(
+                "mwalibContext_read_by_frequency() ERROR: caller-supplied buffer is too small.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::BufferTooSmall;
+        }
+
+        // Populate the buffer which was provided to us by caller
+        output_slice[..data.len()].copy_from_slice(data.as_slice());
+        // Return Success
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
         set_error_message(
-            "mwalibContext_read_by_frequency() ERROR: no data was returned.",
+            &format!(
+                "mwalibContext_read_by_frequency() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
             error_message,
             error_message_length,
         );
-        return 1;
-    }
-
-    // Populate the buffer which was provided to us by caller
-    output_slice[..data.len()].copy_from_slice(data.as_slice());
-    // Return Success
-    0
+        MwalibErrorCode::Other
+    })
 }
 
 /// Free a previously-allocated float* created by mwalibContext_read_by_baseline.
@@ -415,79 +807,55 @@ pub unsafe extern "C" fn mwalibContext_free_read_buffer(
     float_buffer_ptr: *mut c_float,
     float_buffer_len: *const c_longlong,
 ) {
-    if float_buffer_ptr.is_null() {
-        return;
-    }
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if float_buffer_ptr.is_null() {
+            return;
+        }
 
-    drop(Vec::from_raw_parts(
-        float_buffer_ptr,
-        *float_buffer_len as usize,
-        *float_buffer_len as usize,
-    ));
+        drop(Vec::from_raw_parts(
+            float_buffer_ptr,
+            *float_buffer_len as usize,
+            *float_buffer_len as usize,
+        ));
+    }));
 }
 
-///
-/// This a C struct to allow the caller to consume all of the metadata
-///
-#[repr(C)]
-pub struct mwalibMetadata {
-    /// See definition of context::mwalibContext for full description of each attribute
-    pub obsid: u32,
-    pub corr_version: CorrelatorVersion,
-    pub mwa_latitude_radians: f64,
-    pub mwa_longitude_radians: f64,
-    pub mwa_altitude_metres: f64,
-    pub coax_v_factor: f64,
-    pub global_analogue_attenuation_db: f64,
-    pub ra_tile_pointing_degrees: f64,
-    pub dec_tile_pointing_degrees: f64,
-    pub ra_phase_center_degrees: f64,
-    pub dec_phase_center_degrees: f64,
-    pub azimuth_degrees: f64,
-    pub altitude_degrees: f64,
-    pub sun_altitude_degrees: f64,
-    pub sun_distance_degrees: f64,
-    pub moon_distance_degrees: f64,
-    pub jupiter_distance_degrees: f64,
-    pub lst_degrees: f64,
-    pub hour_angle_string: *mut c_char,
-    pub grid_name: *mut c_char,
-    pub grid_number: i32,
-    pub creator: *mut c_char,
-    pub project_id: *mut c_char,
-    pub observation_name: *mut c_char,
-    pub mode: *mut c_char,
-    pub scheduled_start_utc: time_t,
-    pub scheduled_start_mjd: f64,
-    pub scheduled_duration_milliseconds: u64,
-    pub quack_time_duration_milliseconds: u64,
-    pub good_time_unix_milliseconds: u64,
-    pub start_unix_time_milliseconds: u64,
-    pub end_unix_time_milliseconds: u64,
-    pub duration_milliseconds: u64,
-    pub num_timesteps: usize,
-    pub num_antennas: usize,
-    pub num_baselines: usize,
-    pub num_rf_inputs: usize,
-    pub num_antenna_pols: usize,
-    pub num_visibility_pols: usize,
-    pub num_coarse_channels: usize,
-    pub integration_time_milliseconds: u64,
-    pub fine_channel_width_hz: u32,
-    pub observation_bandwidth_hz: u32,
-    pub coarse_channel_width_hz: u32,
-    pub num_fine_channels_per_coarse: usize,
-    pub num_timestep_coarse_channel_bytes: usize,
-    pub num_timestep_coarse_channel_floats: usize,
-    pub num_gpubox_files: usize,
+/// A `*mut mwalibContext` that is only ever touched from one thread at a
+/// time (the background worker spawned by `mwalibContext_read_async_start`,
+/// until the caller observes completion via `mwalibReadHandle_wait`), so it's
+/// safe to hand across the thread boundary despite raw pointers not being
+/// `Send` by default.
+struct SendableContextPtr(*mut mwalibContext);
+unsafe impl Send for SendableContextPtr {}
+
+/// Opaque handle to a background HDU read started by
+/// `mwalibContext_read_async_start`, analogous to a DMA engine's transfer
+/// descriptor: the caller can keep working (or start another transfer into a
+/// second handle) while the read completes on a worker thread, then collect
+/// the result with `mwalibReadHandle_wait`.
+pub struct mwalibReadHandle {
+    /// Receives the worker thread's `read_by_baseline` result exactly once.
+    receiver: std::sync::mpsc::Receiver<Result<Vec<f32>, String>>,
+    /// Joined by `mwalibReadHandle_wait`/`_free` to ensure the worker has
+    /// fully exited before the handle (and the `mwalibContext` it borrowed)
+    /// can be reused.
+    join_handle: Option<std::thread::JoinHandle<()>>,
 }
 
-/// This returns a struct containing the mwalibContext metadata
+/// Start a background read of a single timestep / coarse channel, returning
+/// immediately with a handle the caller can `mwalibReadHandle_wait` on once
+/// it actually needs the data — overlapping the read's FITS I/O with
+/// whatever the caller does in the meantime, the same way double-buffered
+/// DMA overlaps a transfer with compute.
 ///
 /// # Arguments
 ///
 /// * `context_ptr` - pointer to an already populated mwalibContext object.
 ///
+/// * `timestep_index` - item in the timestep array for the desired timestep.
+///
+/// * `coarse_channel_index` - item in the coarse_channel array for the desired coarse channel.
+///
 /// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
 ///
 /// * `error_message_length` - length of error_message char* buffer.
@@ -495,78 +863,643 @@ pub struct mwalibMetadata {
 ///
 /// # Returns
 ///
-/// * A Rust-owned populated mwalibMetadata struct or NULL if there was an error (check error_message)
+/// * A Rust-owned `mwalibReadHandle`, or NULL if the read could not even be started (check error_message).
 ///
 ///
 /// # Safety
 /// * error_message *must* point to an already allocated char* buffer for any error messages.
-/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
-/// * Caller must call mwalibMetadata_free once finished, to free the rust memory.
+/// * context_ptr must point to a populated object from the mwalibContext_new function.
+/// * The caller must not call `mwalibContext_read_by_baseline`/`_read_by_frequency`/`_free`
+///   (or start another async read) against `context_ptr` until this handle has been
+///   passed to `mwalibReadHandle_wait` or `mwalibReadHandle_free`.
+/// * Caller *must* call `mwalibReadHandle_wait` or `mwalibReadHandle_free` to join the
+///   background thread and release the rust memory.
 #[no_mangle]
-pub unsafe extern "C" fn mwalibMetadata_get(
+pub unsafe extern "C" fn mwalibContext_read_async_start(
     context_ptr: *mut mwalibContext,
+    timestep_index: usize,
+    coarse_channel_index: usize,
     error_message: *mut u8,
     error_message_length: size_t,
-) -> *mut mwalibMetadata {
-    if context_ptr.is_null() {
+) -> *mut mwalibReadHandle {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_read_async_start() ERROR: null pointer for context_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return ptr::null_mut();
+        }
+
+        let sendable_context_ptr = SendableContextPtr(context_ptr);
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let join_handle = std::thread::spawn(move || {
+            let sendable_context_ptr = sendable_context_ptr;
+            let context = &mut *sendable_context_ptr.0;
+            let result = context
+                .read_by_baseline(timestep_index, coarse_channel_index)
+                .map_err(|e| format!("{}", e));
+            // The receiver may already have been dropped (e.g. the caller
+            // freed the handle without waiting); there's nothing useful to
+            // do with that failure here.
+            let _ = sender.send(result);
+        });
+
+        Box::into_raw(Box::new(mwalibReadHandle {
+            receiver,
+            join_handle: Some(join_handle),
+        }))
+    }))
+    .unwrap_or_else(|e| {
         set_error_message(
-            "mwalibMetadata_get() ERROR: Warning: null pointer passed in",
+            &format!(
+                "mwalibContext_read_async_start() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
             error_message,
             error_message_length,
         );
-        return ptr::null_mut();
-    }
-    let context = &*context_ptr;
-    let out_context = mwalibMetadata {
-        obsid: context.obsid,
-        corr_version: context.corr_version,
-        mwa_latitude_radians: context.mwa_latitude_radians,
-        mwa_longitude_radians: context.mwa_longitude_radians,
-        mwa_altitude_metres: context.mwa_altitude_metres,
-        coax_v_factor: context.coax_v_factor,
-        global_analogue_attenuation_db: context.global_analogue_attenuation_db,
-        ra_tile_pointing_degrees: context.ra_tile_pointing_degrees,
-        dec_tile_pointing_degrees: context.dec_tile_pointing_degrees,
-        ra_phase_center_degrees: match context.ra_phase_center_degrees {
-            Some(v) => v,
-            None => 0.,
-        },
-        dec_phase_center_degrees: match context.dec_phase_center_degrees {
-            Some(v) => v,
-            None => 0.,
-        },
-        azimuth_degrees: context.azimuth_degrees,
-        altitude_degrees: context.altitude_degrees,
-        sun_altitude_degrees: context.sun_altitude_degrees,
-        sun_distance_degrees: context.sun_distance_degrees,
-        moon_distance_degrees: context.moon_distance_degrees,
-        jupiter_distance_degrees: context.jupiter_distance_degrees,
-        lst_degrees: context.lst_degrees,
-        hour_angle_string: CString::new(String::from(&context.hour_angle_string))
-            .unwrap()
-            .into_raw(),
-        grid_name: CString::new(String::from(&context.grid_name))
-            .unwrap()
-            .into_raw(),
-        grid_number: context.grid_number,
-        creator: CString::new(String::from(&context.creator))
-            .unwrap()
-            .into_raw(),
-        project_id: CString::new(String::from(&context.project_id))
-            .unwrap()
-            .into_raw(),
-        observation_name: CString::new(String::from(&context.observation_name))
-            .unwrap()
-            .into_raw(),
-        mode: CString::new(String::from(&context.mode))
-            .unwrap()
-            .into_raw(),
-        scheduled_start_utc: context.scheduled_start_utc.timestamp(),
-        scheduled_start_mjd: context.scheduled_start_mjd,
-        scheduled_duration_milliseconds: context.scheduled_duration_milliseconds,
-        start_unix_time_milliseconds: context.start_unix_time_milliseconds,
-        end_unix_time_milliseconds: context.end_unix_time_milliseconds,
-        duration_milliseconds: context.duration_milliseconds,
+        ptr::null_mut()
+    })
+}
+
+/// Block until the background read started by `mwalibContext_read_async_start`
+/// completes, then copy its result into `buffer_ptr` exactly as
+/// `mwalibContext_read_by_baseline` would have. Joins (and thereby frees) the
+/// worker thread, but the `mwalibReadHandle` itself must still be released
+/// with `mwalibReadHandle_free`.
+///
+/// # Arguments
+///
+/// * `handle_ptr` - pointer to a `mwalibReadHandle` from `mwalibContext_read_async_start`.
+///
+/// * `buffer_ptr` - pointer to caller-owned and allocated buffer to write data into.
+///
+/// * `buffer_len` - length of `buffer_ptr`.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success, or another `MwalibErrorCode` variant on failure
+///   (check `error_message` for detail)
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * handle_ptr must point to a handle returned by mwalibContext_read_async_start that has not already been waited on.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibReadHandle_wait(
+    handle_ptr: *mut mwalibReadHandle,
+    buffer_ptr: *mut c_float,
+    buffer_len: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        let handle = if handle_ptr.is_null() {
+            set_error_message(
+                "mwalibReadHandle_wait() ERROR: null pointer for handle_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        } else {
+            &mut *handle_ptr
+        };
+
+        if buffer_ptr.is_null() {
+            return MwalibErrorCode::NullPointer;
+        }
+
+        let received = handle.receiver.recv();
+
+        if let Some(join_handle) = handle.join_handle.take() {
+            let _ = join_handle.join();
+        }
+
+        let data = match received {
+            Ok(Ok(data)) => data,
+            Ok(Err(e)) => {
+                set_error_message(&e, error_message, error_message_length);
+                return MwalibErrorCode::CorrelatorReadError;
+            }
+            Err(_) => {
+                set_error_message(
+                    "mwalibReadHandle_wait() ERROR: background read thread exited without a result",
+                    error_message,
+                    error_message_length,
+                );
+                return MwalibErrorCode::Other;
+            }
+        };
+
+        if data.is_empty() {
+            set_error_message(
+                "mwalibReadHandle_wait() ERROR: no data was returned.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NoDataReturned;
+        }
+
+        let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
+        if data.len() > output_slice.len() {
+            set_error_message(
+                "mwalibReadHandle_wait() ERROR: caller-supplied buffer is too small.",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::BufferTooSmall;
+        }
+
+        output_slice[..data.len()].copy_from_slice(data.as_slice());
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibReadHandle_wait() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        MwalibErrorCode::Other
+    })
+}
+
+/// Free a previously-allocated `mwalibReadHandle` struct, joining its
+/// background thread first if `mwalibReadHandle_wait` was never called.
+///
+/// # Arguments
+///
+/// * `handle_ptr` - pointer to an already populated mwalibReadHandle object.
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the mwalibReadHandle object.
+/// * handle_ptr must point to a handle returned by mwalibContext_read_async_start.
+/// * handle_ptr must not have already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibReadHandle_free(handle_ptr: *mut mwalibReadHandle) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if handle_ptr.is_null() {
+            return;
+        }
+
+        let mut handle = Box::from_raw(handle_ptr);
+        if let Some(join_handle) = handle.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }));
+}
+
+/// A self-describing, Rust-owned buffer of visibility samples returned by
+/// `mwalibContext_read_by_baseline_owned`, bundling the sample buffer itself
+/// with the dimensions needed to interpret it so the caller never has to
+/// separately track (or guess) `buffer_len` the way
+/// `mwalibContext_read_by_baseline`/`mwalibContext_free_read_buffer` require.
+///
+/// `data` holds `len` `c_float`s laid out `[baseline][fine_channel][pol][r][i]`.
+#[repr(C)]
+pub struct mwalibVisibilitySet {
+    /// Pointer to the Rust-owned sample buffer.
+    pub data: *mut c_float,
+    /// Number of `c_float` samples `data` points to.
+    pub len: size_t,
+    /// Capacity (in `c_float`s) of the allocation `data` points to; required
+    /// by `mwalibVisibilitySet_free` to reconstruct the exact `Vec` that was
+    /// leaked into this struct.
+    pub capacity: size_t,
+    pub num_baselines: usize,
+    pub num_fine_channels: usize,
+    pub num_visibility_pols: usize,
+}
+
+/// Read a single timestep / coarse channel of MWA data, returning a
+/// Rust-owned, self-describing `mwalibVisibilitySet` instead of requiring the
+/// caller to pre-allocate and guess a `buffer_len` the way
+/// `mwalibContext_read_by_baseline` does.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `timestep_index` - item in the timestep array for the desired timestep.
+///
+/// * `coarse_channel_index` - item in the coarse_channel array for the desired coarse channel.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * A Rust-owned populated `mwalibVisibilitySet` struct, or NULL if there was an error (check error_message).
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated object from the mwalibContext_new function.
+/// * Caller *must* call `mwalibVisibilitySet_free` to release the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibContext_read_by_baseline_owned(
+    context_ptr: *mut mwalibContext,
+    timestep_index: usize,
+    coarse_channel_index: usize,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> *mut mwalibVisibilitySet {
+    catch_unwind(AssertUnwindSafe(|| {
+        let context = if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_read_by_baseline_owned() ERROR: null pointer for context_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return ptr::null_mut();
+        } else {
+            &mut *context_ptr
+        };
+
+        let mut data = match context.read_by_baseline(timestep_index, coarse_channel_index) {
+            Ok(data) => data,
+            Err(e) => {
+                set_error_message(&format!("{}", e), error_message, error_message_length);
+                return ptr::null_mut();
+            }
+        };
+
+        if data.is_empty() {
+            set_error_message(
+                "mwalibContext_read_by_baseline_owned() ERROR: no data was returned.",
+                error_message,
+                error_message_length,
+            );
+            return ptr::null_mut();
+        }
+
+        let visibility_set = mwalibVisibilitySet {
+            data: data.as_mut_ptr(),
+            len: data.len(),
+            capacity: data.capacity(),
+            num_baselines: context.num_baselines,
+            num_fine_channels: context.num_fine_channels_per_coarse,
+            num_visibility_pols: context.num_visibility_pols,
+        };
+        std::mem::forget(data);
+
+        Box::into_raw(Box::new(visibility_set))
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibContext_read_by_baseline_owned() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+/// Free a previously-allocated `mwalibVisibilitySet` struct, including the
+/// sample buffer it owns.
+///
+/// # Arguments
+///
+/// * `visibility_set_ptr` - pointer to an already populated mwalibVisibilitySet object.
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the mwalibVisibilitySet object.
+/// * visibility_set_ptr must point to a populated object from `mwalibContext_read_by_baseline_owned`.
+/// * visibility_set_ptr must not have already been freed.
+#[no_mangle]
+#[cfg_attr(tarpaulin, skip)]
+pub unsafe extern "C" fn mwalibVisibilitySet_free(visibility_set_ptr: *mut mwalibVisibilitySet) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if visibility_set_ptr.is_null() {
+            return;
+        }
+        let visibility_set = Box::from_raw(visibility_set_ptr);
+        drop(Vec::from_raw_parts(
+            visibility_set.data,
+            visibility_set.len,
+            visibility_set.capacity,
+        ));
+    }));
+}
+
+/// Read a contiguous `[timestep_start..timestep_start+timestep_count) x
+/// [coarse_chan_start..coarse_chan_start+coarse_chan_count)` range of HDUs
+/// into one caller-allocated buffer, instead of requiring one
+/// `mwalibContext_read_by_baseline` call per HDU. HDUs are written
+/// contiguously in `[timestep][coarse_channel]` order, each HDU in the usual
+/// `[baseline][fine_channel][pol][r][i]` layout.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `timestep_start` - first timestep index in the requested range.
+///
+/// * `timestep_count` - number of timesteps in the requested range.
+///
+/// * `coarse_chan_start` - first coarse channel index in the requested range.
+///
+/// * `coarse_chan_count` - number of coarse channels in the requested range.
+///
+/// * `buffer_ptr` - pointer to caller-owned and allocated buffer to write data into.
+///
+/// * `buffer_len` - length of `buffer_ptr`.
+///
+/// * `num_hdus_written` - pointer to a `size_t` that is set to the number of HDUs actually written.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success, or another `MwalibErrorCode` variant on failure
+///   (check `error_message` for detail)
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated object from the mwalibContext_new function.
+/// * num_hdus_written must point to an already allocated `size_t`.
+/// * buffer_ptr must point to a caller-owned buffer of at least `buffer_len` floats; the caller frees it.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibContext_read_range_by_baseline(
+    context_ptr: *mut mwalibContext,
+    timestep_start: usize,
+    timestep_count: usize,
+    coarse_chan_start: usize,
+    coarse_chan_count: usize,
+    buffer_ptr: *mut c_float,
+    buffer_len: size_t,
+    num_hdus_written: *mut size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        let context = if context_ptr.is_null() {
+            set_error_message(
+                "mwalibContext_read_range_by_baseline() ERROR: null pointer for context_ptr passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        } else {
+            &mut *context_ptr
+        };
+
+        if buffer_ptr.is_null() || num_hdus_written.is_null() {
+            return MwalibErrorCode::NullPointer;
+        }
+
+        let timestep_end = timestep_start + timestep_count;
+        let coarse_chan_end = coarse_chan_start + coarse_chan_count;
+        if timestep_end > context.num_timesteps || coarse_chan_end > context.num_coarse_channels {
+            set_error_message(
+                &format!(
+                    "mwalibContext_read_range_by_baseline() ERROR: requested range timestep [{}..{}), coarse_channel [{}..{}) is out of bounds (have {} timesteps, {} coarse channels).",
+                    timestep_start, timestep_end, coarse_chan_start, coarse_chan_end,
+                    context.num_timesteps, context.num_coarse_channels
+                ),
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::InvalidIndex;
+        }
+
+        let output_slice = slice::from_raw_parts_mut(buffer_ptr, buffer_len);
+        *num_hdus_written = 0;
+        let mut offset = 0usize;
+
+        for timestep_index in timestep_start..timestep_end {
+            for coarse_channel_index in coarse_chan_start..coarse_chan_end {
+                let data = match context.read_by_baseline(timestep_index, coarse_channel_index) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        set_error_message(&format!("{}", e), error_message, error_message_length);
+                        return MwalibErrorCode::CorrelatorReadError;
+                    }
+                };
+
+                if offset + data.len() > output_slice.len() {
+                    set_error_message(
+                        "mwalibContext_read_range_by_baseline() ERROR: caller-supplied buffer is too small.",
+                        error_message,
+                        error_message_length,
+                    );
+                    return MwalibErrorCode::BufferTooSmall;
+                }
+
+                output_slice[offset..offset + data.len()].copy_from_slice(data.as_slice());
+                offset += data.len();
+                *num_hdus_written += 1;
+            }
+        }
+
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibContext_read_range_by_baseline() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        MwalibErrorCode::Other
+    })
+}
+
+///
+/// This a C struct to allow the caller to consume all of the metadata
+///
+#[repr(C)]
+pub struct mwalibMetadata {
+    /// See definition of context::mwalibContext for full description of each attribute
+    pub obsid: u32,
+    pub corr_version: CorrelatorVersion,
+    pub mwa_latitude_radians: f64,
+    pub mwa_longitude_radians: f64,
+    pub mwa_altitude_metres: f64,
+    pub coax_v_factor: f64,
+    pub global_analogue_attenuation_db: f64,
+    pub ra_tile_pointing_degrees: f64,
+    pub dec_tile_pointing_degrees: f64,
+    pub ra_phase_center_degrees: f64,
+    pub dec_phase_center_degrees: f64,
+    pub azimuth_degrees: f64,
+    pub altitude_degrees: f64,
+    pub sun_altitude_degrees: f64,
+    pub sun_distance_degrees: f64,
+    pub moon_distance_degrees: f64,
+    pub jupiter_distance_degrees: f64,
+    pub lst_degrees: f64,
+    pub hour_angle_string: *mut c_char,
+    pub grid_name: *mut c_char,
+    pub grid_number: i32,
+    pub creator: *mut c_char,
+    pub project_id: *mut c_char,
+    pub observation_name: *mut c_char,
+    pub mode: *mut c_char,
+    pub scheduled_start_utc: time_t,
+    pub scheduled_start_mjd: f64,
+    pub scheduled_duration_milliseconds: u64,
+    pub quack_time_duration_milliseconds: u64,
+    pub good_time_unix_milliseconds: u64,
+    pub start_unix_time_milliseconds: u64,
+    pub end_unix_time_milliseconds: u64,
+    pub duration_milliseconds: u64,
+    pub num_timesteps: usize,
+    pub num_antennas: usize,
+    pub num_baselines: usize,
+    pub num_rf_inputs: usize,
+    pub num_antenna_pols: usize,
+    pub num_visibility_pols: usize,
+    pub num_coarse_channels: usize,
+    pub integration_time_milliseconds: u64,
+    pub fine_channel_width_hz: u32,
+    pub observation_bandwidth_hz: u32,
+    pub coarse_channel_width_hz: u32,
+    pub num_fine_channels_per_coarse: usize,
+    pub num_timestep_coarse_channel_bytes: usize,
+    pub num_timestep_coarse_channel_floats: usize,
+    pub num_gpubox_files: usize,
+}
+
+/// This returns a struct containing the mwalibContext metadata
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * A Rust-owned populated mwalibMetadata struct or NULL if there was an error (check error_message)
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
+/// * Caller must call mwalibMetadata_free once finished, to free the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibMetadata_get(
+    context_ptr: *mut mwalibContext,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> *mut mwalibMetadata {
+    catch_unwind(AssertUnwindSafe(|| {
+        mwalibMetadata_get_inner(context_ptr, error_message, error_message_length)
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibMetadata_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+unsafe fn mwalibMetadata_get_inner(
+    context_ptr: *mut mwalibContext,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> *mut mwalibMetadata {
+    if context_ptr.is_null() {
+        set_error_message(
+            "mwalibMetadata_get() ERROR: Warning: null pointer passed in",
+            error_message,
+            error_message_length,
+        );
+        return ptr::null_mut();
+    }
+    let context = &*context_ptr;
+    let out_context = mwalibMetadata {
+        obsid: context.obsid,
+        corr_version: context.corr_version,
+        mwa_latitude_radians: context.mwa_latitude_radians,
+        mwa_longitude_radians: context.mwa_longitude_radians,
+        mwa_altitude_metres: context.mwa_altitude_metres,
+        coax_v_factor: context.coax_v_factor,
+        global_analogue_attenuation_db: context.global_analogue_attenuation_db,
+        ra_tile_pointing_degrees: context.ra_tile_pointing_degrees,
+        dec_tile_pointing_degrees: context.dec_tile_pointing_degrees,
+        ra_phase_center_degrees: match context.ra_phase_center_degrees {
+            Some(v) => v,
+            None => 0.,
+        },
+        dec_phase_center_degrees: match context.dec_phase_center_degrees {
+            Some(v) => v,
+            None => 0.,
+        },
+        azimuth_degrees: context.azimuth_degrees,
+        altitude_degrees: context.altitude_degrees,
+        sun_altitude_degrees: context.sun_altitude_degrees,
+        sun_distance_degrees: context.sun_distance_degrees,
+        moon_distance_degrees: context.moon_distance_degrees,
+        jupiter_distance_degrees: context.jupiter_distance_degrees,
+        lst_degrees: context.lst_degrees,
+        hour_angle_string: CString::new(String::from(&context.hour_angle_string))
+            .unwrap()
+            .into_raw(),
+        grid_name: CString::new(String::from(&context.grid_name))
+            .unwrap()
+            .into_raw(),
+        grid_number: context.grid_number,
+        creator: CString::new(String::from(&context.creator))
+            .unwrap()
+            .into_raw(),
+        project_id: CString::new(String::from(&context.project_id))
+            .unwrap()
+            .into_raw(),
+        observation_name: CString::new(String::from(&context.observation_name))
+            .unwrap()
+            .into_raw(),
+        mode: CString::new(String::from(&context.mode))
+            .unwrap()
+            .into_raw(),
+        scheduled_start_utc: context.scheduled_start_utc.timestamp(),
+        scheduled_start_mjd: context.scheduled_start_mjd,
+        scheduled_duration_milliseconds: context.scheduled_duration_milliseconds,
+        start_unix_time_milliseconds: context.start_unix_time_milliseconds,
+        end_unix_time_milliseconds: context.end_unix_time_milliseconds,
+        duration_milliseconds: context.duration_milliseconds,
         quack_time_duration_milliseconds: context.quack_time_duration_milliseconds,
         good_time_unix_milliseconds: context.good_time_unix_milliseconds,
         num_timesteps: context.num_timesteps,
@@ -608,10 +1541,12 @@ pub unsafe extern "C" fn mwalibMetadata_get(
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
 pub unsafe extern "C" fn mwalibMetadata_free(metadata_ptr: *mut mwalibMetadata) {
-    if metadata_ptr.is_null() {
-        return;
-    }
-    drop(Box::from_raw(metadata_ptr));
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if metadata_ptr.is_null() {
+            return;
+        }
+        drop(Box::from_raw(metadata_ptr));
+    }));
 }
 
 /// Representation in C of an mwalibRFInput struct
@@ -661,6 +1596,33 @@ pub unsafe extern "C" fn mwalibRFInput_get(
     rf_input_index: size_t,
     error_message: *mut u8,
     error_message_length: size_t,
+) -> *mut mwalibRFInput {
+    catch_unwind(AssertUnwindSafe(|| {
+        mwalibRFInput_get_inner(
+            context_ptr,
+            rf_input_index,
+            error_message,
+            error_message_length,
+        )
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibRFInput_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+unsafe fn mwalibRFInput_get_inner(
+    context_ptr: *mut mwalibContext,
+    rf_input_index: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
 ) -> *mut mwalibRFInput {
     if context_ptr.is_null() {
         set_error_message(
@@ -710,11 +1672,134 @@ pub unsafe extern "C" fn mwalibRFInput_get(
     }
 }
 
-/// Free a previously-allocated `mwalibRFInput` struct.
+/// Free a previously-allocated `mwalibRFInput` struct.
+///
+/// # Arguments
+///
+/// * `rf_input_ptr` - pointer to an already populated mwalibRFInput object
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the mwalibRFInput object
+/// * rf_input_ptr must point to a populated mwalibRFInput object from the mwalibRFInput_new function.
+/// * rf_input_ptr must not have already been freed.
+#[no_mangle]
+#[cfg_attr(tarpaulin, skip)]
+pub unsafe extern "C" fn mwalibRFInput_free(rf_input_ptr: *mut mwalibRFInput) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if rf_input_ptr.is_null() {
+            return;
+        }
+        // Materialise object, so rust will drop it when it hits out of scope
+        let rf_input = Box::from_raw(rf_input_ptr);
+
+        // Also materialise the tile_name string
+        CString::from_raw(rf_input.tile_name);
+        CString::from_raw(rf_input.pol);
+    }));
+}
+
+/// Return all rf_inputs in one contiguous, Rust-owned, `#[repr(C)]` array,
+/// instead of requiring one `mwalibRFInput_get` FFI call (and heap allocation)
+/// per element.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `out_ptr` - pointer to a `*mut mwalibRFInput` that will be set to point at the returned array.
+///
+/// * `out_len` - pointer to a `size_t` that will be set to the length of the returned array.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success (including an empty, non-null array), or another
+///   `MwalibErrorCode` variant on failure (check `error_message` for detail).
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
+/// * out_ptr and out_len must point to already allocated `*mut mwalibRFInput` and `size_t` respectively.
+/// * Caller must call mwalibRFInput_free_all once finished, to free the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibRFInput_get_all(
+    context_ptr: *mut mwalibContext,
+    out_ptr: *mut *mut mwalibRFInput,
+    out_len: *mut size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+            set_error_message(
+                "mwalibRFInput_get_all() ERROR: null pointer passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        }
+        let context = &*context_ptr;
+
+        let mut v = Vec::with_capacity(context.num_rf_inputs);
+        for rf_input_index in 0..context.num_rf_inputs {
+            v.push(mwalibRFInput {
+                input: context.rf_inputs[rf_input_index].input,
+                antenna: context.rf_inputs[rf_input_index].antenna,
+                tile_id: context.rf_inputs[rf_input_index].tile_id,
+                tile_name: CString::new(String::from(&context.rf_inputs[rf_input_index].tile_name))
+                    .unwrap()
+                    .into_raw(),
+                pol: CString::new(String::from(&context.rf_inputs[rf_input_index].pol))
+                    .unwrap()
+                    .into_raw(),
+                electrical_length_m: context.rf_inputs[rf_input_index].electrical_length_m,
+                north_m: context.rf_inputs[rf_input_index].north_m,
+                east_m: context.rf_inputs[rf_input_index].east_m,
+                height_m: context.rf_inputs[rf_input_index].height_m,
+                vcs_order: context.rf_inputs[rf_input_index].vcs_order,
+                subfile_order: context.rf_inputs[rf_input_index].subfile_order,
+                flagged: context.rf_inputs[rf_input_index].flagged,
+            });
+        }
+
+        let mut v = std::mem::ManuallyDrop::new(v);
+        *out_ptr = v.as_mut_ptr();
+        *out_len = v.len();
+
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibRFInput_get_all() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        MwalibErrorCode::Other
+    })
+}
+
+/// Free a previously-allocated array of `mwalibRFInput` structs created by
+/// `mwalibRFInput_get_all`, including each element's owned `tile_name`/`pol` strings.
 ///
 /// # Arguments
 ///
-/// * `rf_input_ptr` - pointer to an already populated mwalibRFInput object
+/// * `ptr` - pointer to the array returned by `mwalibRFInput_get_all`.
+///
+/// * `len` - length of the array, as returned by `mwalibRFInput_get_all`.
 ///
 ///
 /// # Returns
@@ -723,21 +1808,22 @@ pub unsafe extern "C" fn mwalibRFInput_get(
 ///
 ///
 /// # Safety
-/// * This must be called once caller is finished with the mwalibRFInput object
-/// * rf_input_ptr must point to a populated mwalibRFInput object from the mwalibRFInput_new function.
-/// * rf_input_ptr must not have already been freed.
+/// * This must be called once caller is finished with the array.
+/// * ptr/len must be exactly as returned by `mwalibRFInput_get_all`.
+/// * ptr must not have already been freed.
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
-pub unsafe extern "C" fn mwalibRFInput_free(rf_input_ptr: *mut mwalibRFInput) {
-    if rf_input_ptr.is_null() {
-        return;
-    }
-    // Materialise object, so rust will drop it when it hits out of scope
-    let rf_input = Box::from_raw(rf_input_ptr);
-
-    // Also materialise the tile_name string
-    CString::from_raw(rf_input.tile_name);
-    CString::from_raw(rf_input.pol);
+pub unsafe extern "C" fn mwalibRFInput_free_all(ptr: *mut mwalibRFInput, len: size_t) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if ptr.is_null() {
+            return;
+        }
+        let v = Vec::from_raw_parts(ptr, len, len);
+        for rf_input in &v {
+            CString::from_raw(rf_input.tile_name);
+            CString::from_raw(rf_input.pol);
+        }
+    }));
 }
 
 /// Representation in C of an mwalibCoarseChannel struct
@@ -781,6 +1867,33 @@ pub unsafe extern "C" fn mwalibCoarseChannel_get(
     coarse_channel_index: size_t,
     error_message: *mut u8,
     error_message_length: size_t,
+) -> *mut mwalibCoarseChannel {
+    catch_unwind(AssertUnwindSafe(|| {
+        mwalibCoarseChannel_get_inner(
+            context_ptr,
+            coarse_channel_index,
+            error_message,
+            error_message_length,
+        )
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibCoarseChannel_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+unsafe fn mwalibCoarseChannel_get_inner(
+    context_ptr: *mut mwalibContext,
+    coarse_channel_index: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
 ) -> *mut mwalibCoarseChannel {
     if context_ptr.is_null() {
         set_error_message(
@@ -838,10 +1951,123 @@ pub unsafe extern "C" fn mwalibCoarseChannel_get(
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
 pub unsafe extern "C" fn mwalibCoarseChannel_free(coarse_channel_ptr: *mut mwalibCoarseChannel) {
-    if coarse_channel_ptr.is_null() {
-        return;
-    }
-    drop(Box::from_raw(coarse_channel_ptr));
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if coarse_channel_ptr.is_null() {
+            return;
+        }
+        drop(Box::from_raw(coarse_channel_ptr));
+    }));
+}
+
+/// Return all coarse channels in one contiguous, Rust-owned, `#[repr(C)]` array,
+/// instead of requiring one `mwalibCoarseChannel_get` FFI call (and heap allocation)
+/// per element.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `out_ptr` - pointer to a `*mut mwalibCoarseChannel` that will be set to point at the returned array.
+///
+/// * `out_len` - pointer to a `size_t` that will be set to the length of the returned array.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success (including an empty, non-null array), or another
+///   `MwalibErrorCode` variant on failure (check `error_message` for detail).
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
+/// * out_ptr and out_len must point to already allocated `*mut mwalibCoarseChannel` and `size_t` respectively.
+/// * Caller must call mwalibCoarseChannel_free_all once finished, to free the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibCoarseChannel_get_all(
+    context_ptr: *mut mwalibContext,
+    out_ptr: *mut *mut mwalibCoarseChannel,
+    out_len: *mut size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+            set_error_message(
+                "mwalibCoarseChannel_get_all() ERROR: null pointer passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        }
+        let context = &*context_ptr;
+
+        let mut v = Vec::with_capacity(context.num_coarse_channels);
+        for coarse_channel_index in 0..context.num_coarse_channels {
+            v.push(mwalibCoarseChannel {
+                correlator_channel_number: context.coarse_channels[coarse_channel_index]
+                    .correlator_channel_number,
+                receiver_channel_number: context.coarse_channels[coarse_channel_index]
+                    .receiver_channel_number,
+                gpubox_number: context.coarse_channels[coarse_channel_index].gpubox_number,
+                channel_width_hz: context.coarse_channels[coarse_channel_index].channel_width_hz,
+                channel_start_hz: context.coarse_channels[coarse_channel_index].channel_start_hz,
+                channel_centre_hz: context.coarse_channels[coarse_channel_index].channel_centre_hz,
+                channel_end_hz: context.coarse_channels[coarse_channel_index].channel_end_hz,
+            });
+        }
+
+        let mut v = std::mem::ManuallyDrop::new(v);
+        *out_ptr = v.as_mut_ptr();
+        *out_len = v.len();
+
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibCoarseChannel_get_all() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        MwalibErrorCode::Other
+    })
+}
+
+/// Free a previously-allocated array of `mwalibCoarseChannel` structs created by
+/// `mwalibCoarseChannel_get_all`.
+///
+/// # Arguments
+///
+/// * `ptr` - pointer to the array returned by `mwalibCoarseChannel_get_all`.
+///
+/// * `len` - length of the array, as returned by `mwalibCoarseChannel_get_all`.
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the array.
+/// * ptr/len must be exactly as returned by `mwalibCoarseChannel_get_all`.
+/// * ptr must not have already been freed.
+#[no_mangle]
+#[cfg_attr(tarpaulin, skip)]
+pub unsafe extern "C" fn mwalibCoarseChannel_free_all(ptr: *mut mwalibCoarseChannel, len: size_t) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if ptr.is_null() {
+            return;
+        }
+        drop(Vec::from_raw_parts(ptr, len, len));
+    }));
 }
 
 /// Representation in C of an mwalibAntenna struct
@@ -881,6 +2107,33 @@ pub unsafe extern "C" fn mwalibAntenna_get(
     antenna_index: size_t,
     error_message: *mut u8,
     error_message_length: size_t,
+) -> *mut mwalibAntenna {
+    catch_unwind(AssertUnwindSafe(|| {
+        mwalibAntenna_get_inner(
+            context_ptr,
+            antenna_index,
+            error_message,
+            error_message_length,
+        )
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibAntenna_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+unsafe fn mwalibAntenna_get_inner(
+    context_ptr: *mut mwalibContext,
+    antenna_index: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
 ) -> *mut mwalibAntenna {
     if context_ptr.is_null() {
         set_error_message(
@@ -936,15 +2189,127 @@ pub unsafe extern "C" fn mwalibAntenna_get(
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
 pub unsafe extern "C" fn mwalibAntenna_free(antenna_ptr: *mut mwalibAntenna) {
-    if antenna_ptr.is_null() {
-        return;
-    }
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if antenna_ptr.is_null() {
+            return;
+        }
+
+        // Materialise object, so rust will drop it when it hits out of scope
+        let antenna = Box::from_raw(antenna_ptr);
+
+        // Also materialise the tile_name string
+        CString::from_raw(antenna.tile_name);
+    }));
+}
+
+/// Return all antennas in one contiguous, Rust-owned, `#[repr(C)]` array,
+/// instead of requiring one `mwalibAntenna_get` FFI call (and heap allocation)
+/// per element.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `out_ptr` - pointer to a `*mut mwalibAntenna` that will be set to point at the returned array.
+///
+/// * `out_len` - pointer to a `size_t` that will be set to the length of the returned array.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success (including an empty, non-null array), or another
+///   `MwalibErrorCode` variant on failure (check `error_message` for detail).
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
+/// * out_ptr and out_len must point to already allocated `*mut mwalibAntenna` and `size_t` respectively.
+/// * Caller must call mwalibAntenna_free_all once finished, to free the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibAntenna_get_all(
+    context_ptr: *mut mwalibContext,
+    out_ptr: *mut *mut mwalibAntenna,
+    out_len: *mut size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+            set_error_message(
+                "mwalibAntenna_get_all() ERROR: null pointer passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        }
+        let context = &*context_ptr;
+
+        let mut v = Vec::with_capacity(context.num_antennas);
+        for antenna_index in 0..context.num_antennas {
+            v.push(mwalibAntenna {
+                antenna: context.antennas[antenna_index].antenna,
+                tile_id: context.antennas[antenna_index].tile_id,
+                tile_name: CString::new(String::from(&context.antennas[antenna_index].tile_name))
+                    .unwrap()
+                    .into_raw(),
+            });
+        }
 
-    // Materialise object, so rust will drop it when it hits out of scope
-    let antenna = Box::from_raw(antenna_ptr);
+        let mut v = std::mem::ManuallyDrop::new(v);
+        *out_ptr = v.as_mut_ptr();
+        *out_len = v.len();
+
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibAntenna_get_all() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        MwalibErrorCode::Other
+    })
+}
 
-    // Also materialise the tile_name string
-    CString::from_raw(antenna.tile_name);
+/// Free a previously-allocated array of `mwalibAntenna` structs created by
+/// `mwalibAntenna_get_all`, including each element's owned `tile_name` string.
+///
+/// # Arguments
+///
+/// * `ptr` - pointer to the array returned by `mwalibAntenna_get_all`.
+///
+/// * `len` - length of the array, as returned by `mwalibAntenna_get_all`.
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the array.
+/// * ptr/len must be exactly as returned by `mwalibAntenna_get_all`.
+/// * ptr must not have already been freed.
+#[no_mangle]
+#[cfg_attr(tarpaulin, skip)]
+pub unsafe extern "C" fn mwalibAntenna_free_all(ptr: *mut mwalibAntenna, len: size_t) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if ptr.is_null() {
+            return;
+        }
+        let v = Vec::from_raw_parts(ptr, len, len);
+        for antenna in &v {
+            CString::from_raw(antenna.tile_name);
+        }
+    }));
 }
 
 ///
@@ -979,48 +2344,178 @@ pub struct mwalibTimeStep {
 /// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
 /// * Caller must call mwalibTimeStep_free once finished, to free the rust memory.
 #[no_mangle]
-pub unsafe extern "C" fn mwalibTimeStep_get(
+pub unsafe extern "C" fn mwalibTimeStep_get(
+    context_ptr: *mut mwalibContext,
+    timestep_index: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> *mut mwalibTimeStep {
+    catch_unwind(AssertUnwindSafe(|| {
+        mwalibTimeStep_get_inner(
+            context_ptr,
+            timestep_index,
+            error_message,
+            error_message_length,
+        )
+    }))
+    .unwrap_or_else(|e| {
+        set_error_message(
+            &format!(
+                "mwalibTimeStep_get() ERROR: internal panic: {}",
+                panic_message(e)
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    })
+}
+
+unsafe fn mwalibTimeStep_get_inner(
+    context_ptr: *mut mwalibContext,
+    timestep_index: size_t,
+    error_message: *mut u8,
+    error_message_length: size_t,
+) -> *mut mwalibTimeStep {
+    if context_ptr.is_null() {
+        set_error_message(
+            "mwalibTimeStep_get() ERROR: null pointer passed in",
+            error_message,
+            error_message_length,
+        );
+        return ptr::null_mut();
+    }
+    let context = &*context_ptr;
+
+    if timestep_index < context.num_timesteps {
+        let out_timestep = mwalibTimeStep {
+            unix_time_ms: context.timesteps[timestep_index].unix_time_ms,
+        };
+
+        Box::into_raw(Box::new(out_timestep))
+    } else {
+        set_error_message(
+            &format!(
+                "mwalibTimeStep_get() ERROR: timestep index must be between 0 ({}) and {} ({}).",
+                context.timesteps[0].unix_time_ms as f32 / 1000.,
+                context.num_timesteps - 1,
+                context.timesteps[context.num_timesteps - 1].unix_time_ms as f32 / 1000.,
+            ),
+            error_message,
+            error_message_length,
+        );
+        ptr::null_mut()
+    }
+}
+
+/// Free a previously-allocated `mwalibTimeStep` struct.
+///
+/// # Arguments
+///
+/// * `timestep_ptr` - pointer to an already populated mwalibTimeStep object
+///
+///
+/// # Returns
+///
+/// * Nothing
+///
+///
+/// # Safety
+/// * This must be called once caller is finished with the mwalibTimeStep object
+/// * timestep_ptr must point to a populated mwalibTimeStep object from the mwalibTimeStep_new function.
+/// * timestep_ptr must not have already been freed.
+#[no_mangle]
+#[cfg_attr(tarpaulin, skip)]
+pub unsafe extern "C" fn mwalibTimeStep_free(timestep_ptr: *mut mwalibTimeStep) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if timestep_ptr.is_null() {
+            return;
+        }
+        drop(Box::from_raw(timestep_ptr));
+    }));
+}
+
+/// Return all timesteps in one contiguous, Rust-owned, `#[repr(C)]` array,
+/// instead of requiring one `mwalibTimeStep_get` FFI call (and heap allocation)
+/// per element.
+///
+/// # Arguments
+///
+/// * `context_ptr` - pointer to an already populated mwalibContext object.
+///
+/// * `out_ptr` - pointer to a `*mut mwalibTimeStep` that will be set to point at the returned array.
+///
+/// * `out_len` - pointer to a `size_t` that will be set to the length of the returned array.
+///
+/// * `error_message` - pointer to already allocated buffer for any error messages to be returned to the caller.
+///
+/// * `error_message_length` - length of error_message char* buffer.
+///
+///
+/// # Returns
+///
+/// * `MwalibErrorCode::Success` on success (including an empty, non-null array), or another
+///   `MwalibErrorCode` variant on failure (check `error_message` for detail).
+///
+///
+/// # Safety
+/// * error_message *must* point to an already allocated char* buffer for any error messages.
+/// * context_ptr must point to a populated mwalibContext object from the mwalibContext_new function.
+/// * out_ptr and out_len must point to already allocated `*mut mwalibTimeStep` and `size_t` respectively.
+/// * Caller must call mwalibTimeStep_free_all once finished, to free the rust memory.
+#[no_mangle]
+pub unsafe extern "C" fn mwalibTimeStep_get_all(
     context_ptr: *mut mwalibContext,
-    timestep_index: size_t,
+    out_ptr: *mut *mut mwalibTimeStep,
+    out_len: *mut size_t,
     error_message: *mut u8,
     error_message_length: size_t,
-) -> *mut mwalibTimeStep {
-    if context_ptr.is_null() {
-        set_error_message(
-            "mwalibTimeStep_get() ERROR: null pointer passed in",
-            error_message,
-            error_message_length,
-        );
-        return ptr::null_mut();
-    }
-    let context = &*context_ptr;
+) -> MwalibErrorCode {
+    catch_unwind(AssertUnwindSafe(|| {
+        if context_ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+            set_error_message(
+                "mwalibTimeStep_get_all() ERROR: null pointer passed in",
+                error_message,
+                error_message_length,
+            );
+            return MwalibErrorCode::NullPointer;
+        }
+        let context = &*context_ptr;
 
-    if timestep_index < context.num_timesteps {
-        let out_timestep = mwalibTimeStep {
-            unix_time_ms: context.timesteps[timestep_index].unix_time_ms,
-        };
+        let mut v = Vec::with_capacity(context.num_timesteps);
+        for timestep_index in 0..context.num_timesteps {
+            v.push(mwalibTimeStep {
+                unix_time_ms: context.timesteps[timestep_index].unix_time_ms,
+            });
+        }
 
-        Box::into_raw(Box::new(out_timestep))
-    } else {
+        let mut v = std::mem::ManuallyDrop::new(v);
+        *out_ptr = v.as_mut_ptr();
+        *out_len = v.len();
+
+        MwalibErrorCode::Success
+    }))
+    .unwrap_or_else(|e| {
         set_error_message(
             &format!(
-                "mwalibTimeStep_get() ERROR: timestep index must be between 0 ({}) and {} ({}).",
-                context.timesteps[0].unix_time_ms as f32 / 1000.,
-                context.num_timesteps - 1,
-                context.timesteps[context.num_timesteps - 1].unix_time_ms as f32 / 1000.,
+                "mwalibTimeStep_get_all() ERROR: internal panic: {}",
+                panic_message(e)
             ),
             error_message,
             error_message_length,
         );
-        ptr::null_mut()
-    }
+        MwalibErrorCode::Other
+    })
 }
 
-/// Free a previously-allocated `mwalibTimeStep` struct.
+/// Free a previously-allocated array of `mwalibTimeStep` structs created by
+/// `mwalibTimeStep_get_all`.
 ///
 /// # Arguments
 ///
-/// * `timestep_ptr` - pointer to an already populated mwalibTimeStep object
+/// * `ptr` - pointer to the array returned by `mwalibTimeStep_get_all`.
+///
+/// * `len` - length of the array, as returned by `mwalibTimeStep_get_all`.
 ///
 ///
 /// # Returns
@@ -1029,16 +2524,18 @@ pub unsafe extern "C" fn mwalibTimeStep_get(
 ///
 ///
 /// # Safety
-/// * This must be called once caller is finished with the mwalibTimeStep object
-/// * timestep_ptr must point to a populated mwalibTimeStep object from the mwalibTimeStep_new function.
-/// * timestep_ptr must not have already been freed.
+/// * This must be called once caller is finished with the array.
+/// * ptr/len must be exactly as returned by `mwalibTimeStep_get_all`.
+/// * ptr must not have already been freed.
 #[no_mangle]
 #[cfg_attr(tarpaulin, skip)]
-pub unsafe extern "C" fn mwalibTimeStep_free(timestep_ptr: *mut mwalibTimeStep) {
-    if timestep_ptr.is_null() {
-        return;
-    }
-    drop(Box::from_raw(timestep_ptr));
+pub unsafe extern "C" fn mwalibTimeStep_free_all(ptr: *mut mwalibTimeStep, len: size_t) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if ptr.is_null() {
+            return;
+        }
+        drop(Vec::from_raw_parts(ptr, len, len));
+    }));
 }
 
 #[cfg(test)]
@@ -1055,6 +2552,85 @@ mod tests {
         assert_eq!(buffer, CString::new("hello world").unwrap());
     }
 
+    #[test]
+    fn test_last_error_thread_local() {
+        // A failed antenna lookup on a null context both truncates into the
+        // caller's tiny buffer and still stashes the full message, retrievable
+        // via either the copy-into-buffer or the pointer-returning API.
+        let error_message = CString::new("     ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+
+        unsafe {
+            let antenna = Box::from_raw(mwalibAntenna_get(
+                std::ptr::null_mut(),
+                0,
+                error_message_ptr,
+                6,
+            ));
+            let antenna_ptr = Box::into_raw(antenna);
+            assert_eq!(antenna_ptr.is_null(), true);
+
+            let expected_error: &str = &"mwalibAntenna_get() ERROR:";
+
+            let last_error_ptr = mwalib_get_last_error();
+            assert_eq!(last_error_ptr.is_null(), false);
+            let last_error = CStr::from_ptr(last_error_ptr).to_str().unwrap();
+            assert_eq!(&last_error[0..expected_error.len()], expected_error);
+
+            let mut buffer = vec![0u8; last_error.len() + 1];
+            let len =
+                mwalibContext_get_last_error_message(buffer.as_mut_ptr(), buffer.len() as size_t);
+            assert_eq!(len, last_error.len());
+            let copied = CStr::from_ptr(buffer.as_ptr() as *const c_char)
+                .to_str()
+                .unwrap();
+            assert_eq!(copied, last_error);
+        }
+    }
+
+    #[test]
+    fn test_mwalibcontext_get_mock() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        unsafe {
+            let context = mwalibContext_get_mock(128, 4, 24);
+            assert_eq!(context.is_null(), false);
+
+            let antenna = Box::from_raw(mwalibAntenna_get(
+                context,
+                2,
+                error_message_ptr,
+                error_len,
+            ));
+            assert_eq!(antenna.tile_id, 2);
+            assert_eq!(
+                CString::from_raw(antenna.tile_name),
+                CString::new("Tile002").unwrap()
+            );
+
+            let timestep = Box::from_raw(mwalibTimeStep_get(
+                context,
+                1,
+                error_message_ptr,
+                error_len,
+            ));
+            assert_eq!(timestep.unix_time_ms, 1000);
+
+            let coarse_channel = Box::from_raw(mwalibCoarseChannel_get(
+                context,
+                0,
+                error_message_ptr,
+                error_len,
+            ));
+            assert_eq!(coarse_channel.receiver_channel_number, 1);
+
+            mwalibContext_free(context);
+        }
+    }
+
     // Metadata
     #[test]
     fn test_mwalibmetadata_get_valid() {
@@ -1679,4 +3255,228 @@ mod tests {
             );
         }
     }
+
+    // Bulk array getters
+    #[test]
+    fn test_mwalibantenna_get_all_valid() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        let metafits_file =
+            CString::new("test_files/1101503312_1_timestep/1101503312.metafits").unwrap();
+        let metafits_file_ptr = metafits_file.as_ptr();
+
+        let gpubox_file = CString::new(
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits",
+        )
+        .unwrap();
+        let mut gpubox_files: Vec<*const c_char> = Vec::new();
+        gpubox_files.push(gpubox_file.as_ptr());
+        let gpubox_files_ptr = gpubox_files.as_ptr() as *mut *const c_char;
+
+        unsafe {
+            let context = mwalibContext_get(
+                metafits_file_ptr,
+                gpubox_files_ptr,
+                1,
+                error_message_ptr,
+                60,
+            );
+
+            let context_ptr = context.as_mut();
+            assert_eq!(context_ptr.is_some(), true);
+
+            let mut out_ptr: *mut mwalibAntenna = std::ptr::null_mut();
+            let mut out_len: size_t = 0;
+            let result = mwalibAntenna_get_all(
+                context,
+                &mut out_ptr,
+                &mut out_len,
+                error_message_ptr,
+                error_len,
+            );
+
+            assert_eq!(result, MwalibErrorCode::Success);
+            assert_eq!(out_len, (*context).num_antennas);
+
+            let antennas = slice::from_raw_parts(out_ptr, out_len);
+            assert_eq!(antennas[2].tile_id, 13);
+
+            mwalibAntenna_free_all(out_ptr, out_len);
+        }
+    }
+
+    #[test]
+    fn test_mwalibantenna_get_all_null_context() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        unsafe {
+            let mut out_ptr: *mut mwalibAntenna = std::ptr::null_mut();
+            let mut out_len: size_t = 0;
+            let result = mwalibAntenna_get_all(
+                std::ptr::null_mut(),
+                &mut out_ptr,
+                &mut out_len,
+                error_message_ptr,
+                error_len,
+            );
+
+            assert_eq!(result, MwalibErrorCode::NullPointer);
+            assert_eq!(out_ptr.is_null(), true);
+            let expected_error: &str = &"mwalibAntenna_get_all() ERROR:";
+            assert_eq!(
+                error_message.into_string().unwrap()[0..expected_error.len()],
+                *expected_error
+            );
+        }
+    }
+
+    #[test]
+    fn test_mwalibtimestep_get_all_valid() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        let metafits_file =
+            CString::new("test_files/1101503312_1_timestep/1101503312.metafits").unwrap();
+        let metafits_file_ptr = metafits_file.as_ptr();
+
+        let gpubox_file = CString::new(
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits",
+        )
+        .unwrap();
+        let mut gpubox_files: Vec<*const c_char> = Vec::new();
+        gpubox_files.push(gpubox_file.as_ptr());
+        let gpubox_files_ptr = gpubox_files.as_ptr() as *mut *const c_char;
+
+        unsafe {
+            let context = mwalibContext_get(
+                metafits_file_ptr,
+                gpubox_files_ptr,
+                1,
+                error_message_ptr,
+                60,
+            );
+
+            let context_ptr = context.as_mut();
+            assert_eq!(context_ptr.is_some(), true);
+
+            let mut out_ptr: *mut mwalibTimeStep = std::ptr::null_mut();
+            let mut out_len: size_t = 0;
+            let result = mwalibTimeStep_get_all(
+                context,
+                &mut out_ptr,
+                &mut out_len,
+                error_message_ptr,
+                error_len,
+            );
+
+            assert_eq!(result, MwalibErrorCode::Success);
+            assert_eq!(out_len, (*context).num_timesteps);
+
+            mwalibTimeStep_free_all(out_ptr, out_len);
+        }
+    }
+
+    #[test]
+    fn test_mwalibcoarsechannel_get_all_valid() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        let metafits_file =
+            CString::new("test_files/1101503312_1_timestep/1101503312.metafits").unwrap();
+        let metafits_file_ptr = metafits_file.as_ptr();
+
+        let gpubox_file = CString::new(
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits",
+        )
+        .unwrap();
+        let mut gpubox_files: Vec<*const c_char> = Vec::new();
+        gpubox_files.push(gpubox_file.as_ptr());
+        let gpubox_files_ptr = gpubox_files.as_ptr() as *mut *const c_char;
+
+        unsafe {
+            let context = mwalibContext_get(
+                metafits_file_ptr,
+                gpubox_files_ptr,
+                1,
+                error_message_ptr,
+                60,
+            );
+
+            let context_ptr = context.as_mut();
+            assert_eq!(context_ptr.is_some(), true);
+
+            let mut out_ptr: *mut mwalibCoarseChannel = std::ptr::null_mut();
+            let mut out_len: size_t = 0;
+            let result = mwalibCoarseChannel_get_all(
+                context,
+                &mut out_ptr,
+                &mut out_len,
+                error_message_ptr,
+                error_len,
+            );
+
+            assert_eq!(result, MwalibErrorCode::Success);
+            assert_eq!(out_len, (*context).num_coarse_channels);
+
+            mwalibCoarseChannel_free_all(out_ptr, out_len);
+        }
+    }
+
+    #[test]
+    fn test_mwalibrfinput_get_all_valid() {
+        let error_message =
+            CString::new("                                                            ").unwrap();
+        let error_message_ptr = error_message.as_ptr() as *mut u8;
+        let error_len: size_t = 60;
+
+        let metafits_file =
+            CString::new("test_files/1101503312_1_timestep/1101503312.metafits").unwrap();
+        let metafits_file_ptr = metafits_file.as_ptr();
+
+        let gpubox_file = CString::new(
+            "test_files/1101503312_1_timestep/1101503312_20141201210818_gpubox01_00.fits",
+        )
+        .unwrap();
+        let mut gpubox_files: Vec<*const c_char> = Vec::new();
+        gpubox_files.push(gpubox_file.as_ptr());
+        let gpubox_files_ptr = gpubox_files.as_ptr() as *mut *const c_char;
+
+        unsafe {
+            let context = mwalibContext_get(
+                metafits_file_ptr,
+                gpubox_files_ptr,
+                1,
+                error_message_ptr,
+                60,
+            );
+
+            let context_ptr = context.as_mut();
+            assert_eq!(context_ptr.is_some(), true);
+
+            let mut out_ptr: *mut mwalibRFInput = std::ptr::null_mut();
+            let mut out_len: size_t = 0;
+            let result = mwalibRFInput_get_all(
+                context,
+                &mut out_ptr,
+                &mut out_len,
+                error_message_ptr,
+                error_len,
+            );
+
+            assert_eq!(result, MwalibErrorCode::Success);
+            assert_eq!(out_len, (*context).num_rf_inputs);
+
+            mwalibRFInput_free_all(out_ptr, out_len);
+        }
+    }
 }
@@ -134,6 +134,43 @@ fn test_set_error_message() {
     assert_eq!(buffer, CString::new("hello world").unwrap());
 }
 
+//
+// ABI layout tests - these mirror the compile-time `static_assert_size!`
+// checks next to each struct's definition, so a size regression shows up
+// here too (with the usual test output) rather than only as a build error.
+//
+#[test]
+fn test_ffi_struct_sizes() {
+    assert_eq!(std::mem::size_of::<Rfinput>(), 160);
+    assert_eq!(std::mem::size_of::<TimeStep>(), 32);
+    assert_eq!(std::mem::size_of::<VisibilityPol>(), 8);
+}
+
+#[test]
+fn test_mwalib_get_last_error() {
+    let message = "mwalibAntenna_get() ERROR: index out of bounds";
+
+    // set_error_message() is also given a truncating buffer here, to check
+    // that the thread-local copy is unaffected by the caller's buffer size.
+    let buffer = CString::new("x").unwrap();
+    let buffer_ptr = buffer.as_ptr() as *mut u8;
+    set_error_message(message, buffer_ptr, 1);
+
+    unsafe {
+        let last_error_ptr = mwalib_get_last_error();
+        assert!(!last_error_ptr.is_null());
+        assert_eq!(CStr::from_ptr(last_error_ptr).to_str().unwrap(), message);
+
+        let mut full_buffer = vec![0u8; message.len() + 1];
+        let full_len = mwalib_get_last_error_message(full_buffer.as_mut_ptr(), full_buffer.len());
+        assert_eq!(full_len, message.len());
+        assert_eq!(
+            CStr::from_bytes_with_nul(&full_buffer).unwrap().to_str().unwrap(),
+            message
+        );
+    }
+}
+
 //
 // Metafits context Tests
 //
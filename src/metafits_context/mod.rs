@@ -8,6 +8,7 @@ The main interface to MWA data.
 use std::fmt;
 
 use chrono::{DateTime, Duration, FixedOffset};
+use hifitime::Epoch;
 
 use crate::antenna::*;
 use crate::baseline::*;
@@ -58,6 +59,180 @@ impl fmt::Display for CorrelatorVersion {
     }
 }
 
+/// Whether, and how, cable length corrections have already been applied to
+/// the visibilities in this observation. Mapped from the integer `CABLEDEL`
+/// keyword in the metafits.
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CableDelaysApplied {
+    /// No cable length corrections have been applied (`CABLEDEL` absent, or 0)
+    NoCableDelaysApplied,
+    /// Cable and receiver clock delays have been applied (`CABLEDEL` == 1)
+    CableAndRecClockDelaysApplied,
+    /// Cable and receiver clock delays have been applied by the system
+    /// (`CABLEDEL` == 2)
+    CableAndRecClockDelaysAppliedBySystem,
+}
+
+impl CableDelaysApplied {
+    /// Whether any cable length correction (of any variant) has been applied.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if this is any variant other than `NoCableDelaysApplied`.
+    ///
+    pub fn were_cable_delays_applied(self) -> bool {
+        self != CableDelaysApplied::NoCableDelaysApplied
+    }
+}
+
+/// Whether geometric (w-term) delays have already been applied to the visibilities
+/// in this observation and, if so, which phase centre they were computed against.
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GeometricDelaysApplied {
+    /// No geometric delays have been applied
+    No,
+    /// Geometric delays have been applied, phased to the zenith
+    Zenith,
+    /// Geometric delays have been applied, phased to the tile pointing centre
+    TilePointing,
+    /// Geometric delays have been applied, phased to the phase centre
+    PhaseCentre,
+}
+
+/// Implements fmt::Display for CableDelaysApplied enum
+impl fmt::Display for CableDelaysApplied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CableDelaysApplied::NoCableDelaysApplied => "not applied",
+                CableDelaysApplied::CableAndRecClockDelaysApplied => "applied",
+                CableDelaysApplied::CableAndRecClockDelaysAppliedBySystem => {
+                    "applied (by system)"
+                }
+            }
+        )
+    }
+}
+
+/// Implements fmt::Display for GeometricDelaysApplied enum
+impl fmt::Display for GeometricDelaysApplied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GeometricDelaysApplied::No => "not applied",
+                GeometricDelaysApplied::Zenith => "applied (zenith)",
+                GeometricDelaysApplied::TilePointing => "applied (tile pointing centre)",
+                GeometricDelaysApplied::PhaseCentre => "applied (phase centre)",
+            }
+        )
+    }
+}
+
+/// Geodetic array position (longitude, latitude, height above the WGS84
+/// ellipsoid), in the same shape marlu's `LatLngHeight` expects.
+///
+/// mwalib does not itself depend on marlu; this struct exists purely so a
+/// caller that does can build a `marlu::LatLngHeight` from these fields
+/// without re-deriving the mapping from `MetafitsContext`.
+#[derive(Clone, Copy, Debug)]
+pub struct LatLngHeight {
+    /// Longitude, in radians.
+    pub longitude_rad: f64,
+    /// Latitude, in radians.
+    pub latitude_rad: f64,
+    /// Height above the WGS84 ellipsoid, in metres.
+    pub height_metres: f64,
+}
+
+/// A Right Ascension/Declination position, in the same shape marlu's `RADec`
+/// expects. See [`LatLngHeight`] for why this is a local type.
+#[derive(Clone, Copy, Debug)]
+pub struct RADec {
+    /// Right ascension, in radians.
+    pub ra_rad: f64,
+    /// Declination, in radians.
+    pub dec_rad: f64,
+}
+
+/// A tile's geodetic XYZ position relative to the array centre, in metres, in
+/// the same shape marlu's `XyzGeodetic` expects. See [`LatLngHeight`] for why
+/// this is a local type.
+#[derive(Clone, Copy, Debug)]
+pub struct XyzGeodetic {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Observation-level metadata assembled by `MetafitsContext::to_obs_context`,
+/// in the same shape marlu's `ObsContext` expects. This lets a caller that
+/// already knows how to build a uvfits/measurement set writer from a marlu
+/// `ObsContext` do so straight from a `MetafitsContext`, without re-deriving
+/// the phase centre/array position/tile position mapping itself.
+#[derive(Clone, Debug)]
+pub struct ObsContext {
+    /// The observation ID (GPS time of the start of the observation).
+    pub obsid: u32,
+    /// The phase centre of this observation (falls back to the tile pointing
+    /// centre if no explicit phase centre is recorded).
+    pub phase_centre: RADec,
+    /// The tile pointing centre of this observation.
+    pub pointing_centre: RADec,
+    /// The position of the array.
+    pub array_position: LatLngHeight,
+    /// Each tile's geodetic XYZ position, in the same order as `antennas`.
+    pub ant_positions_geodetic: Vec<XyzGeodetic>,
+    /// Each tile's name, in the same order as `antennas`.
+    pub ant_names: Vec<String>,
+}
+
+/// Per-visibility-file metadata assembled by `MetafitsContext::to_vis_context`,
+/// in the same shape marlu's `VisContext` expects - the timestep/channel/
+/// baseline shape a uvfits or measurement set writer needs to lay out a data
+/// HDU.
+#[derive(Clone, Debug)]
+pub struct VisContext {
+    /// Number of timesteps selected, starting at `start_timestamp`.
+    pub num_sel_timesteps: usize,
+    /// Epoch of the first selected timestep's integration centre.
+    pub start_timestamp: Epoch,
+    /// Correlator integration time, in nanoseconds.
+    pub int_time_ns: u64,
+    /// Number of fine channels selected, per coarse channel.
+    pub num_sel_chans: usize,
+    /// Frequency of the centre of the first selected fine channel, in Hz.
+    pub start_freq_hz: f64,
+    /// Width of a fine channel, in Hz.
+    pub freq_resolution_hz: f64,
+    /// Selected baselines, as (ant1_index, ant2_index) pairs in `MetafitsContext::baselines` order.
+    pub sel_baselines: Vec<(usize, usize)>,
+    /// Number of polarisation combinations in the visibilities e.g. XX,XY,YX,YY == 4.
+    pub num_vis_pols: usize,
+}
+
+/// The result of precessing a phase centre and LST from J2000 to the mean
+/// equator/equinox of the observation epoch. See
+/// `MetafitsContext::precess_to_epoch`.
+#[derive(Clone, Copy, Debug)]
+pub struct PrecessionInfo {
+    /// Precessed right ascension of the phase centre, in radians.
+    pub ra_rad: f64,
+    /// Precessed declination of the phase centre, in radians.
+    pub dec_rad: f64,
+    /// Precessed Local Sidereal Time, in radians.
+    pub lst_rad: f64,
+    /// The J2000-to-date precession rotation matrix used to derive the
+    /// fields above. Apply this (via `misc::rotate_vec3`) to tile XYZ
+    /// positions to precess them the same way.
+    pub precession_matrix: Matrix3,
+}
+
 /// `mwalib` metafits context. This represents the basic metadata for the observation.
 ///
 #[derive(Clone, Debug)]
@@ -82,6 +257,12 @@ pub struct MetafitsContext {
     pub sched_end_mjd: f64,
     /// Scheduled duration of observation
     pub sched_duration_ms: u64,
+    /// Array reference position latitude (radians)
+    pub array_latitude_rad: f64,
+    /// Array reference position longitude (radians)
+    pub array_longitude_rad: f64,
+    /// Array reference position altitude (metres)
+    pub array_altitude_m: f64,
     /// RA tile pointing
     pub ra_tile_pointing_degrees: f64,
     /// DEC tile pointing
@@ -140,6 +321,10 @@ pub struct MetafitsContext {
     pub delays: Vec<u32>,
     /// ATTEN_DB  // global analogue attenuation, in dB
     pub global_analogue_attenuation_db: f64,
+    /// Whether cable length corrections have already been applied to the visibilities
+    pub cable_delays_applied: CableDelaysApplied,
+    /// Whether geometric delays have already been applied to the visibilities, and if so against which phase centre
+    pub geometric_delays_applied: GeometricDelaysApplied,
     /// Seconds of bad data after observation starts
     pub quack_time_duration_ms: u64,
     /// OBSID+QUACKTIM as Unix timestamp (first good timestep)
@@ -176,6 +361,46 @@ pub struct MetafitsContext {
     pub metafits_filename: String,
 }
 
+/// Coordinate and Jones-matrix metadata accessors, built on the per-RF-input
+/// fields already parsed from the metafits (`north_m`/`east_m`/`height_m`,
+/// `dipole_delays`, `dipole_gains`). These let calibration code compute UVWs
+/// and primary-beam corrections directly from an `Antenna`, without
+/// re-opening the metafits with a separate FITS reader.
+impl Antenna {
+    /// This tile's East/North/Height offset from the array centre, in
+    /// metres, as `(east_m, north_m, height_m)`. Both polarisations of a
+    /// tile share the same physical position; this uses the X-pol `Rfinput`.
+    pub fn enh_m(&self) -> (f64, f64, f64) {
+        (
+            self.rfinput_x.east_m,
+            self.rfinput_x.north_m,
+            self.rfinput_x.height_m,
+        )
+    }
+
+    /// The 16 beamformer dipole delays for this tile's X polarisation.
+    pub fn dipole_delays_x(&self) -> &[u32] {
+        &self.rfinput_x.dipole_delays
+    }
+
+    /// The 16 beamformer dipole delays for this tile's Y polarisation.
+    pub fn dipole_delays_y(&self) -> &[u32] {
+        &self.rfinput_y.dipole_delays
+    }
+
+    /// The analogue beamformer dipole gains for this tile's X polarisation
+    /// (one per dipole; `0` for a dead dipole).
+    pub fn dipole_gains_x(&self) -> &[f64] {
+        &self.rfinput_x.dipole_gains
+    }
+
+    /// The analogue beamformer dipole gains for this tile's Y polarisation
+    /// (one per dipole; `0` for a dead dipole).
+    pub fn dipole_gains_y(&self) -> &[f64] {
+        &self.rfinput_y.dipole_gains
+    }
+}
+
 impl MetafitsContext {
     /// From a path to a metafits file, create a `MetafitsContext`.
     ///
@@ -340,6 +565,37 @@ impl MetafitsContext {
         let global_analogue_attenuation_db: f64 =
             get_required_fits_key!(&mut metafits_fptr, &metafits_hdu, "ATTEN_DB")?;
 
+        // CABLEDEL describes whether, and how, cable length corrections have
+        // already been applied to the visibilities. Observations older than
+        // this keyword being introduced don't have it, so treat it as absent
+        // == not applied.
+        let cable_delays_applied: CableDelaysApplied = {
+            let cable_delays_key: Option<i32> =
+                get_optional_fits_key!(&mut metafits_fptr, &metafits_hdu, "CABLEDEL")?;
+            match cable_delays_key {
+                Some(1) => CableDelaysApplied::CableAndRecClockDelaysApplied,
+                Some(2) => CableDelaysApplied::CableAndRecClockDelaysAppliedBySystem,
+                _ => CableDelaysApplied::NoCableDelaysApplied,
+            }
+        };
+
+        // GEODEL describes whether geometric delays have already been applied to
+        // the visibilities and, if so, which phase centre they were computed for.
+        let geometric_delays_applied: GeometricDelaysApplied = {
+            let geometric_delays_key: Option<String> =
+                get_optional_fits_key!(&mut metafits_fptr, &metafits_hdu, "GEODEL")?;
+            match geometric_delays_key.as_deref().map(str::trim) {
+                Some(s) if s.eq_ignore_ascii_case("zenith") => GeometricDelaysApplied::Zenith,
+                Some(s) if s.eq_ignore_ascii_case("tile pointing") => {
+                    GeometricDelaysApplied::TilePointing
+                }
+                Some(s) if s.eq_ignore_ascii_case("phase centre") => {
+                    GeometricDelaysApplied::PhaseCentre
+                }
+                _ => GeometricDelaysApplied::No,
+            }
+        };
+
         // observation bandwidth (read from metafits in MHz)
         let metafits_observation_bandwidth_hz: u32 = {
             let bw: f64 = get_required_fits_key!(&mut metafits_fptr, &metafits_hdu, "BANDWDTH")?;
@@ -365,6 +621,12 @@ impl MetafitsContext {
         let num_corr_fine_chans_per_coarse =
             (metafits_coarse_chan_width_hz / fine_chan_width_hz) as usize;
 
+        // MWA array reference position. This isn't in the metafits, so use the
+        // well known position of the MWA site.
+        let array_latitude_rad: f64 = dms_to_degrees(-26, 42, 11.94986).to_radians(); // -26d42m11.94986s
+        let array_longitude_rad: f64 = dms_to_degrees(116, 40, 14.93485).to_radians(); // 116d40m14.93485s
+        let array_altitude_m: f64 = 377.827;
+
         Ok(MetafitsContext {
             obs_id: obsid,
             sched_start_gps_time_ms: scheduled_start_gpstime_ms,
@@ -376,6 +638,9 @@ impl MetafitsContext {
             sched_start_mjd: scheduled_start_mjd,
             sched_end_mjd: scheduled_end_mjd,
             sched_duration_ms: scheduled_duration_ms,
+            array_latitude_rad,
+            array_longitude_rad,
+            array_altitude_m,
             ra_tile_pointing_degrees,
             dec_tile_pointing_degrees,
             ra_phase_center_degrees,
@@ -405,6 +670,8 @@ impl MetafitsContext {
             receivers,
             delays,
             global_analogue_attenuation_db,
+            cable_delays_applied,
+            geometric_delays_applied,
             quack_time_duration_ms,
             good_time_unix_ms,
             good_time_gps_ms,
@@ -471,6 +738,357 @@ impl MetafitsContext {
 
         Ok(coarse_chans)
     }
+
+    /// Compute the UVW coordinates (in metres) of every baseline in
+    /// `self.baselines`, for a given phase centre and Local Sidereal Time.
+    ///
+    /// Each tile's local East-North-Height position is first rotated into
+    /// local XYZ (relative to the array centre) via [`enh_to_local_xyz`], then
+    /// each baseline's XYZ difference is rotated into UVW via [`xyz_to_uvw`]
+    /// using the hour angle (LST - RA) and declination of the phase centre.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase_centre_ra_rad` - Right ascension of the phase centre, in radians.
+    ///
+    /// * `phase_centre_dec_rad` - Declination of the phase centre, in radians.
+    ///
+    /// * `lst_rad` - Local Sidereal Time, in radians.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A `Vec` of `[f64; 3]` (u, v, w) coordinates in metres, one per entry of `self.baselines`, in the same order.
+    ///
+    pub fn get_baseline_uvw(
+        &self,
+        phase_centre_ra_rad: f64,
+        phase_centre_dec_rad: f64,
+        lst_rad: f64,
+    ) -> Vec<[f64; 3]> {
+        let hour_angle_rad = lst_rad - phase_centre_ra_rad;
+
+        // Local XYZ (relative to the array centre) of each antenna, indexed
+        // the same way as `self.antennas`. X and Y pols of the same tile
+        // share a position; use the x pol's.
+        let antenna_local_xyz: Vec<(f64, f64, f64)> = self
+            .antennas
+            .iter()
+            .map(|antenna| {
+                enh_to_local_xyz(
+                    antenna.rfinput_x.north_m,
+                    antenna.rfinput_x.east_m,
+                    antenna.rfinput_x.height_m,
+                    self.array_latitude_rad,
+                )
+            })
+            .collect();
+
+        self.baselines
+            .iter()
+            .map(|baseline| {
+                let (x1, y1, z1) = antenna_local_xyz[baseline.ant1_index];
+                let (x2, y2, z2) = antenna_local_xyz[baseline.ant2_index];
+
+                let (u, v, w) = xyz_to_uvw(
+                    x2 - x1,
+                    y2 - y1,
+                    z2 - z1,
+                    hour_angle_rad,
+                    phase_centre_dec_rad,
+                );
+
+                [u, v, w]
+            })
+            .collect()
+    }
+
+    /// Precess the J2000 phase centre and this observation's LST to the mean
+    /// equator/equinox of the observation epoch (`self.sched_start_mjd`).
+    ///
+    /// Returns `None` if this observation has no recorded phase centre (see
+    /// `ra_phase_center_degrees`/`dec_phase_center_degrees`).
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PrecessionInfo)` with the precessed RA/Dec/LST and the rotation matrix used to derive them, or `None` if there is no phase centre.
+    ///
+    pub fn precess_to_epoch(&self) -> Option<PrecessionInfo> {
+        let ra_rad = self.ra_phase_center_degrees?.to_radians();
+        let dec_rad = self.dec_phase_center_degrees?.to_radians();
+
+        let precession_matrix = precession_matrix(self.sched_start_mjd);
+
+        let j2000_vec = (
+            dec_rad.cos() * ra_rad.cos(),
+            dec_rad.cos() * ra_rad.sin(),
+            dec_rad.sin(),
+        );
+
+        let (x, y, z) = rotate_vec3(precession_matrix, j2000_vec);
+
+        let precessed_dec_rad = z.asin();
+        let precessed_ra_rad = y.atan2(x);
+
+        // The same accumulated precession in RA applies equally to the LST.
+        let precessed_lst_rad = self.lst_rad + (precessed_ra_rad - ra_rad);
+
+        Some(PrecessionInfo {
+            ra_rad: precessed_ra_rad,
+            dec_rad: precessed_dec_rad,
+            lst_rad: precessed_lst_rad,
+            precession_matrix,
+        })
+    }
+
+    /// The scheduled start of the observation, as a [`hifitime::Epoch`].
+    pub fn sched_start_epoch(&self) -> Epoch {
+        Epoch::from_unix_seconds(0.)
+            + hifitime::Duration::from_total_nanoseconds(
+                self.sched_start_unix_time_ms as i128 * 1_000_000,
+            )
+    }
+
+    /// The scheduled end of the observation, as a [`hifitime::Epoch`].
+    pub fn sched_end_epoch(&self) -> Epoch {
+        Epoch::from_unix_seconds(0.)
+            + hifitime::Duration::from_total_nanoseconds(
+                self.sched_end_unix_time_ms as i128 * 1_000_000,
+            )
+    }
+
+    /// The first good timestep of the observation (i.e. after the post-start
+    /// "quack time"), as a [`hifitime::Epoch`].
+    pub fn good_time_epoch(&self) -> Epoch {
+        Epoch::from_unix_seconds(0.)
+            + hifitime::Duration::from_total_nanoseconds(self.good_time_unix_ms as i128 * 1_000_000)
+    }
+
+    /// The first good timestep of the observation (i.e. after the post-start
+    /// "quack time"), expressed as GPS time, as a [`hifitime::Epoch`].
+    pub fn good_time_gps_epoch(&self) -> Epoch {
+        Epoch::from_gpst_seconds(0.)
+            + hifitime::Duration::from_total_nanoseconds(self.good_time_gps_ms as i128 * 1_000_000)
+    }
+
+    /// The epochs at the centre of each correlator integration, from the
+    /// first good timestep through to the scheduled end of the observation,
+    /// at `corr_int_time_ms` cadence.
+    ///
+    /// This centralises the GPS/UTC timestamp arithmetic that downstream
+    /// writers (e.g. uvfits/measurement set) need per-timestep, rather than
+    /// each caller re-deriving it from the millisecond scheduling fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `corr_int_time_ms` - The correlator dump cadence, in milliseconds.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A `Vec<Epoch>` of integration-centre timestamps, one per timestep.
+    ///
+    pub fn timestep_centroids(&self, corr_int_time_ms: u64) -> Vec<Epoch> {
+        let int_time_ns = corr_int_time_ms as i128 * 1_000_000;
+        let half_int_time_ns = int_time_ns / 2;
+
+        let start_ns = self.good_time_unix_ms as i128 * 1_000_000;
+        let end_ns = self.sched_end_unix_time_ms as i128 * 1_000_000;
+
+        let mut centroids = Vec::new();
+        let mut timestep_start_ns = start_ns;
+        while timestep_start_ns < end_ns {
+            let centroid_ns = timestep_start_ns + half_int_time_ns;
+            centroids.push(
+                Epoch::from_unix_seconds(0.)
+                    + hifitime::Duration::from_total_nanoseconds(centroid_ns),
+            );
+            timestep_start_ns += int_time_ns;
+        }
+
+        centroids
+    }
+
+    /// Package this observation's phase centre, array position and tile
+    /// layout into an [`ObsContext`], in the same shape marlu's `ObsContext`
+    /// expects.
+    ///
+    /// # Returns
+    ///
+    /// * A populated [`ObsContext`].
+    ///
+    pub fn to_obs_context(&self) -> ObsContext {
+        let array_position = LatLngHeight {
+            longitude_rad: self.array_longitude_rad,
+            latitude_rad: self.array_latitude_rad,
+            height_metres: self.array_altitude_m,
+        };
+
+        let ant_positions_geodetic = self
+            .antennas
+            .iter()
+            .map(|antenna| {
+                let (x, y, z) = enh_to_local_xyz(
+                    antenna.rfinput_x.north_m,
+                    antenna.rfinput_x.east_m,
+                    antenna.rfinput_x.height_m,
+                    self.array_latitude_rad,
+                );
+                XyzGeodetic { x, y, z }
+            })
+            .collect();
+
+        let ant_names = self
+            .antennas
+            .iter()
+            .map(|antenna| antenna.tile_name.clone())
+            .collect();
+
+        let pointing_centre = RADec {
+            ra_rad: self.ra_tile_pointing_degrees.to_radians(),
+            dec_rad: self.dec_tile_pointing_degrees.to_radians(),
+        };
+
+        // Fall back to the tile pointing centre for observations that don't
+        // have an explicit phase centre recorded (e.g. drift scans).
+        let phase_centre = match (self.ra_phase_center_degrees, self.dec_phase_center_degrees) {
+            (Some(ra), Some(dec)) => RADec {
+                ra_rad: ra.to_radians(),
+                dec_rad: dec.to_radians(),
+            },
+            _ => pointing_centre,
+        };
+
+        ObsContext {
+            obsid: self.obs_id,
+            phase_centre,
+            pointing_centre,
+            array_position,
+            ant_positions_geodetic,
+            ant_names,
+        }
+    }
+
+    /// Package this observation's timestep/channel/baseline shape into a
+    /// [`VisContext`], in the same shape marlu's `VisContext` expects.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_timesteps` - Number of correlator timesteps to include, starting from the first good timestep.
+    ///
+    /// * `num_chans_per_coarse` - Number of fine channels per coarse channel to include.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A populated [`VisContext`].
+    ///
+    pub fn to_vis_context(&self, num_timesteps: usize, num_chans_per_coarse: usize) -> VisContext {
+        let sel_baselines = self
+            .baselines
+            .iter()
+            .map(|baseline| (baseline.ant1_index, baseline.ant2_index))
+            .collect();
+
+        VisContext {
+            num_sel_timesteps: num_timesteps,
+            start_timestamp: self.good_time_epoch()
+                + hifitime::Duration::from_total_nanoseconds(
+                    self.corr_int_time_ms as i128 * 1_000_000 / 2,
+                ),
+            int_time_ns: self.corr_int_time_ms * 1_000_000,
+            num_sel_chans: num_chans_per_coarse,
+            start_freq_hz: self.centre_freq_hz as f64 - (self.obs_bandwidth_hz as f64 / 2.0)
+                + (self.corr_fine_chan_width_hz as f64 / 2.0),
+            freq_resolution_hz: self.corr_fine_chan_width_hz as f64,
+            sel_baselines,
+            num_vis_pols: self.num_visibility_pols,
+        }
+    }
+
+    /// Build a new `MetafitsContext` that only exposes the given subset of
+    /// antennas (tiles), analogous to a `--sel-ants` selection. `antennas`
+    /// and `rf_inputs` are filtered down to the selection, and `baselines`/
+    /// `num_baselines` are recomputed so that baseline-indexed data only
+    /// covers the baselines formed by the selected tiles.
+    ///
+    /// NOTE: an analogous `CorrelatorContext::new_with_antenna_selection`
+    /// would also need to re-slice each HDU's visibilities by baseline, but
+    /// `CorrelatorContext` is not defined in mwalib; this method gives a
+    /// caller that does have such a context the filtered metadata it needs
+    /// to do that re-slicing itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `selected_antenna_indices` - Indices into `self.antennas` of the antennas to keep.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// * A `Result` containing the filtered `MetafitsContext`, or a `MwalibError::InvalidAntennaSelection` if an index is out of range or duplicated.
+    ///
+    pub fn with_antenna_selection(
+        &self,
+        selected_antenna_indices: &[usize],
+    ) -> Result<Self, MwalibError> {
+        let num_ants = self.antennas.len();
+
+        let mut seen_indices = std::collections::HashSet::with_capacity(selected_antenna_indices.len());
+        for &index in selected_antenna_indices {
+            if index >= num_ants || !seen_indices.insert(index) {
+                return Err(MwalibError::InvalidAntennaSelection { index, num_ants });
+            }
+        }
+
+        let antennas: Vec<Antenna> = selected_antenna_indices
+            .iter()
+            .map(|&index| self.antennas[index].clone())
+            .collect();
+
+        let rf_inputs: Vec<Rfinput> = antennas
+            .iter()
+            .flat_map(|antenna| [antenna.rfinput_x.clone(), antenna.rfinput_y.clone()])
+            .collect();
+
+        let num_antennas = antennas.len();
+        let baselines = Baseline::populate_baselines(num_antennas);
+        let num_baselines = baselines.len();
+
+        Ok(MetafitsContext {
+            num_ants: num_antennas,
+            antennas,
+            num_rf_inputs: rf_inputs.len(),
+            rf_inputs,
+            num_baselines,
+            baselines,
+            ..self.clone()
+        })
+    }
+
+    /// The array centre reference position, as geocentric (ECEF) X/Y/Z in
+    /// metres, for beam/coordinate work that needs it in that frame rather
+    /// than the geodetic lat/lon/height `array_*` fields.
+    ///
+    /// # Returns
+    ///
+    /// * A tuple of (X, Y, Z) geocentric coordinates of the array centre, in metres.
+    ///
+    pub fn array_centre_geocentric_xyz_m(&self) -> (f64, f64, f64) {
+        geodetic_to_geocentric_xyz(
+            self.array_latitude_rad,
+            self.array_longitude_rad,
+            self.array_altitude_m,
+        )
+    }
+
+    // NOTE: a `write_metadata_uvfits` convenience (opening a uvfits, writing
+    // the antenna/AIPS AN table and header keywords from `ObsContext`/
+    // `VisContext`) is intentionally not included here. Doing so needs either
+    // a uvfits-writing dependency (e.g. marlu's `UvfitsWriter`) or a new FITS
+    // group/random-parameters table writer, neither of which exist anywhere
+    // in mwalib yet; `to_obs_context`/`to_vis_context` above give callers
+    // (e.g. Birli/hyperdrive, which already own such writers) everything
+    // they need to do so themselves.
 }
 
 /// Implements fmt::Display for MetafitsContext struct
@@ -493,6 +1111,10 @@ impl fmt::Display for MetafitsContext {
     obsid:                    {obsid},
     mode:                     {mode},
 
+    Array latitude:           {array_lat} degrees,
+    Array longitude:          {array_lon} degrees,
+    Array altitude:           {array_alt} m,
+
     Correlator Mode:
     fine channel resolution:  {fcw} kHz,
     integration time:         {int_time:.2} s
@@ -504,6 +1126,8 @@ impl fmt::Display for MetafitsContext {
     Receivers:                {receivers:?},
     Delays:                   {delays:?},
     Global attenuation:       {atten} dB,
+    Cable delays:             {cable_delays},
+    Geometric delays:         {geo_delays},
 
     Scheduled start (UNIX)    {sched_start_unix},
     Scheduled end (UNIX)      {sched_end_unix},
@@ -550,12 +1174,17 @@ impl fmt::Display for MetafitsContext {
     metafits filename:        {meta},
 )"#,
             obsid = self.obs_id,
+            array_lat = self.array_latitude_rad.to_degrees(),
+            array_lon = self.array_longitude_rad.to_degrees(),
+            array_alt = self.array_altitude_m,
             creator = self.creator,
             project_id = self.project_id,
             obs_name = self.obs_name,
             receivers = self.receivers,
             delays = self.delays,
             atten = self.global_analogue_attenuation_db,
+            cable_delays = self.cable_delays_applied,
+            geo_delays = self.geometric_delays_applied,
             sched_start_unix = self.sched_start_unix_time_ms as f64 / 1e3,
             sched_end_unix = self.sched_end_unix_time_ms as f64 / 1e3,
             sched_start_gps = self.sched_start_gps_time_ms as f64 / 1e3,
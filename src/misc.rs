@@ -2,9 +2,99 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-/// Given the number of antennas, calculate the number of baselines (cross+autos)
-pub fn get_baseline_count(antennas: u16) -> u16 {
-    antennas * (antennas + 1) / 2
+use std::collections::HashSet;
+
+use bitmaps::Bitmap as Bitmap64;
+
+/// A dense, heap-allocated bitmap indexed by baseline number. The `bitmaps`
+/// crate's `Bitmap<N>` is fixed-size at compile time, which a baseline count
+/// (tens of thousands, for a full-size MWA array) can't be; this chunks the
+/// baseline index space into 64-bit `bitmaps::Bitmap` words so it can size to
+/// an arbitrary number of baselines at runtime.
+pub struct Bitmap {
+    words: Vec<Bitmap64<64>>,
+    len: usize,
+}
+
+impl Bitmap {
+    fn new(len: usize) -> Self {
+        let num_words = (len + 63) / 64;
+        Bitmap {
+            words: vec![Bitmap64::new(); num_words],
+            len,
+        }
+    }
+
+    fn set(&mut self, index: usize, value: bool) {
+        self.words[index / 64].set(index % 64, value);
+    }
+
+    /// Whether the baseline at `index` is flagged.
+    pub fn get(&self, index: usize) -> bool {
+        self.words[index / 64].get(index % 64)
+    }
+
+    /// The number of baselines this bitmap covers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this bitmap covers zero baselines.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of flagged (set) baselines.
+    pub fn count_flagged(&self) -> usize {
+        self.words.iter().map(|word| word.len()).sum()
+    }
+
+    /// Iterate the baseline indices that are *not* flagged, in ascending order.
+    pub fn iter_unflagged(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |&index| !self.get(index))
+    }
+}
+
+/// Which baseline products a correlator configuration emits. Most MWA
+/// correlator configurations emit autos as well as cross-correlations, but
+/// some emit cross-correlations only; this parameterises
+/// [`get_baseline_count`], [`get_antennas_from_baseline`] and
+/// [`get_baseline_from_antennas`] over both layouts so callers don't have to
+/// maintain their own index math for each.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BaselineOrder {
+    /// Upper-triangle-including-autos ordering: `N*(N+1)/2` baselines, with
+    /// `(ant1, ant1)` autocorrelations included.
+    CrossAndAuto,
+    /// Upper-triangle-excluding-autos ordering: `N*(N-1)/2` baselines;
+    /// `ant1 == ant2` never occurs.
+    CrossOnly,
+}
+
+/// Given the number of antennas, calculate the number of baselines, per `order`.
+pub fn get_baseline_count(order: BaselineOrder, antennas: u16) -> u16 {
+    match order {
+        BaselineOrder::CrossAndAuto => antennas * (antennas + 1) / 2,
+        BaselineOrder::CrossOnly => antennas * (antennas - 1) / 2,
+    }
+}
+
+/// The baseline index of the first baseline in row `ant1`, for the given
+/// `order`.
+///
+/// * `CrossAndAuto`: row `ant1` starts with the `(ant1, ant1)`
+///   autocorrelation; `S(ant1) = ant1*N - ant1*(ant1-1)/2`.
+/// * `CrossOnly`: row `ant1` starts with `(ant1, ant1+1)` and has `N-1-ant1`
+///   entries; `S(ant1) = ant1*(2N-ant1-1)/2`.
+fn baseline_row_start(order: BaselineOrder, ant1: usize, num_antennas: usize) -> usize {
+    if ant1 == 0 {
+        return 0;
+    }
+
+    match order {
+        BaselineOrder::CrossAndAuto => ant1 * num_antennas - ant1 * (ant1 - 1) / 2,
+        BaselineOrder::CrossOnly => ant1 * (2 * num_antennas - ant1 - 1) / 2,
+    }
 }
 
 /// Given a baseline index, return a tuple of (ant1,ant2) for a std right upper triangle e.g. (where N is num antennas)
@@ -20,17 +110,665 @@ pub fn get_baseline_count(antennas: u16) -> u16 {
 /// 2,2
 /// ...
 /// N-1,N-1
-pub fn get_antennas_from_baseline(baseline: usize, num_antennas: usize) -> Option<(usize, usize)> {
-    let mut baseline_index = 0;
-    for ant1 in 0..num_antennas {
-        for ant2 in ant1..num_antennas {
-            if baseline_index == baseline {
-                return Some((ant1, ant2));
+///
+/// (for `BaselineOrder::CrossOnly`, the `ant1,ant1` autocorrelation entries above are omitted)
+///
+/// This is a closed-form O(1) computation rather than a scan of the
+/// triangle: `ant1` is found by solving `S(ant1) <= baseline < S(ant1+1)` for
+/// the relevant quadratic (see [`baseline_row_start`]), then `ant2` follows
+/// from `baseline - S(ant1)`.
+pub fn get_antennas_from_baseline(
+    order: BaselineOrder,
+    baseline: usize,
+    num_antennas: usize,
+) -> Option<(usize, usize)> {
+    if num_antennas == 0 {
+        return None;
+    }
+
+    let total_baselines = match order {
+        BaselineOrder::CrossAndAuto => num_antennas * (num_antennas + 1) / 2,
+        BaselineOrder::CrossOnly => num_antennas * (num_antennas - 1) / 2,
+    };
+    if baseline >= total_baselines {
+        return None;
+    }
+
+    let n = num_antennas as f64;
+    let b = baseline as f64;
+
+    let mut ant1 = match order {
+        BaselineOrder::CrossAndAuto => {
+            let two_n_plus_1 = 2.0 * n + 1.0;
+            (((two_n_plus_1 - (two_n_plus_1 * two_n_plus_1 - 8.0 * b).sqrt()) / 2.0).floor())
+                as usize
+        }
+        BaselineOrder::CrossOnly => {
+            let two_n_minus_1 = 2.0 * n - 1.0;
+            (((two_n_minus_1 - (two_n_minus_1 * two_n_minus_1 - 8.0 * b).sqrt()) / 2.0).floor())
+                as usize
+        }
+    };
+
+    // Guard against floating-point rounding putting us one row early/late at
+    // a row boundary.
+    while ant1 > 0 && baseline_row_start(order, ant1, num_antennas) > baseline {
+        ant1 -= 1;
+    }
+    while baseline_row_start(order, ant1 + 1, num_antennas) <= baseline {
+        ant1 += 1;
+    }
+
+    let offset = baseline - baseline_row_start(order, ant1, num_antennas);
+    let ant2 = match order {
+        BaselineOrder::CrossAndAuto => ant1 + offset,
+        BaselineOrder::CrossOnly => ant1 + 1 + offset,
+    };
+
+    Some((ant1, ant2))
+}
+
+/// The inverse of [`get_antennas_from_baseline`]: given a pair of antenna
+/// indices (in the same ordering as `order`), return the corresponding
+/// baseline index.
+///
+/// Returns `None` if either index is `>= num_antennas`, if `ant2 < ant1`, or
+/// (for `BaselineOrder::CrossOnly`) if `ant1 == ant2`.
+pub fn get_baseline_from_antennas(
+    order: BaselineOrder,
+    ant1: usize,
+    ant2: usize,
+    num_antennas: usize,
+) -> Option<usize> {
+    if ant1 >= num_antennas || ant2 >= num_antennas || ant2 < ant1 {
+        return None;
+    }
+    if order == BaselineOrder::CrossOnly && ant1 == ant2 {
+        return None;
+    }
+
+    let offset = match order {
+        BaselineOrder::CrossAndAuto => ant2 - ant1,
+        BaselineOrder::CrossOnly => ant2 - ant1 - 1,
+    };
+
+    Some(baseline_row_start(order, ant1, num_antennas) + offset)
+}
+
+/// Build a dense, baseline-indexed [`Bitmap`] of flags from a set of flagged
+/// antenna (tile) indices: a baseline is flagged whenever either of its two
+/// antennas is flagged. This is the mask correction loops need to cheaply
+/// skip dead baselines, and what downstream mwaf flag files need as a
+/// per-baseline mask.
+///
+/// # Arguments
+///
+/// * `order` - The baseline product layout (autos included or cross-only).
+///
+/// * `flagged_antennas` - Indices of the flagged antennas.
+///
+/// * `num_antennas` - Total number of antennas in the array.
+///
+///
+/// # Returns
+///
+/// * A [`Bitmap`], one bit per baseline (in `get_antennas_from_baseline` order), set where that baseline touches a flagged antenna.
+///
+pub fn flagged_baselines_from_antennas(
+    order: BaselineOrder,
+    flagged_antennas: &[usize],
+    num_antennas: usize,
+) -> Bitmap {
+    let flagged: HashSet<usize> = flagged_antennas.iter().copied().collect();
+
+    let num_baselines = match order {
+        BaselineOrder::CrossAndAuto => num_antennas * (num_antennas + 1) / 2,
+        BaselineOrder::CrossOnly => num_antennas * num_antennas.saturating_sub(1) / 2,
+    };
+
+    let mut bitmap = Bitmap::new(num_baselines);
+
+    for baseline in 0..num_baselines {
+        if let Some((ant1, ant2)) = get_antennas_from_baseline(order, baseline, num_antennas) {
+            if flagged.contains(&ant1) || flagged.contains(&ant2) {
+                bitmap.set(baseline, true);
             }
-            baseline_index += 1;
         }
     }
 
-    // Baseline was not found at all
-    None
-}
\ No newline at end of file
+    bitmap
+}
+
+/// A group of baselines whose physical separation vectors coincide within
+/// some tolerance - i.e. redundant baselines, as used by redundant-baseline
+/// calibration.
+#[derive(Debug, Clone)]
+pub struct RedundantGroup {
+    /// Indices (in `get_antennas_from_baseline` order) of the baselines in this group.
+    pub baseline_indices: Vec<usize>,
+    /// The mean (Δx, Δy, Δz) separation vector of the group's members, in metres.
+    pub mean_separation_m: [f64; 3],
+}
+
+/// Group baselines by their physical separation vector (Δx, Δy, Δz), to
+/// within `tolerance_m`, for redundant-baseline calibration/averaging. The
+/// per-baseline geometry this computes is the same that [`MetafitsContext`]'s
+/// UVW routine needs, so callers doing both can share one pass over
+/// `antenna_positions`.
+///
+/// # Arguments
+///
+/// * `order` - The baseline product layout (autos included or cross-only).
+///
+/// * `antenna_positions` - Each antenna's local ENU/XYZ `[x, y, z]` position, in metres, indexed the same way as `self.antennas`.
+///
+/// * `num_antennas` - Total number of antennas in the array.
+///
+/// * `tolerance_m` - Baselines whose separation vectors round to the same value at this tolerance (in metres) are grouped together.
+///
+///
+/// # Returns
+///
+/// * A `Vec<RedundantGroup>`, one per distinct quantized separation vector.
+///
+pub fn group_redundant_baselines(
+    order: BaselineOrder,
+    antenna_positions: &[[f64; 3]],
+    num_antennas: usize,
+    tolerance_m: f64,
+) -> Vec<RedundantGroup> {
+    let num_baselines = match order {
+        BaselineOrder::CrossAndAuto => num_antennas * (num_antennas + 1) / 2,
+        BaselineOrder::CrossOnly => num_antennas * num_antennas.saturating_sub(1) / 2,
+    };
+
+    // Quantized (Δx, Δy, Δz) -> (member baseline indices, running sum of the
+    // unquantized separation vector).
+    let mut groups: std::collections::BTreeMap<(i64, i64, i64), (Vec<usize>, [f64; 3])> =
+        std::collections::BTreeMap::new();
+
+    for baseline in 0..num_baselines {
+        let (ant1, ant2) = match get_antennas_from_baseline(order, baseline, num_antennas) {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        let separation_m = [
+            antenna_positions[ant2][0] - antenna_positions[ant1][0],
+            antenna_positions[ant2][1] - antenna_positions[ant1][1],
+            antenna_positions[ant2][2] - antenna_positions[ant1][2],
+        ];
+
+        let key = (
+            (separation_m[0] / tolerance_m).round() as i64,
+            (separation_m[1] / tolerance_m).round() as i64,
+            (separation_m[2] / tolerance_m).round() as i64,
+        );
+
+        let entry = groups.entry(key).or_insert_with(|| (Vec::new(), [0.0; 3]));
+        entry.0.push(baseline);
+        entry.1[0] += separation_m[0];
+        entry.1[1] += separation_m[1];
+        entry.1[2] += separation_m[2];
+    }
+
+    groups
+        .into_values()
+        .map(|(baseline_indices, sum_m)| {
+            let count = baseline_indices.len() as f64;
+            RedundantGroup {
+                baseline_indices,
+                mean_separation_m: [sum_m[0] / count, sum_m[1] / count, sum_m[2] / count],
+            }
+        })
+        .collect()
+}
+
+/// Which polarisation a correlator input corresponds to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InputPol {
+    /// X polarisation.
+    X,
+    /// Y polarisation.
+    Y,
+}
+
+/// The antenna (tile) and polarisation a single correlator input, in M&C
+/// wire order, carries.
+#[derive(Debug, Clone, Copy)]
+pub struct InputMapping {
+    /// Antenna index (in the clean `0..num_antennas` numbering this module's
+    /// triangular indexing assumes).
+    pub antenna: usize,
+    /// Polarisation this input carries.
+    pub pol: InputPol,
+}
+
+/// The M&C wire order for a correlator: `inputs[i]` is the antenna/pol that
+/// raw correlator input `i` carries. The MWA digital system delivers data in
+/// this order, which differs from the clean `0..num_antennas` numbering
+/// [`get_antennas_from_baseline`]/[`get_baseline_from_antennas`] assume; this
+/// lets callers translate between the two without reordering visibilities
+/// themselves.
+pub struct InputOrder {
+    inputs: Vec<InputMapping>,
+}
+
+impl InputOrder {
+    /// Build an `InputOrder` from the per-input antenna/pol mapping, in wire order.
+    pub fn new(inputs: Vec<InputMapping>) -> Self {
+        InputOrder { inputs }
+    }
+
+    /// The number of distinct antennas referenced by this input order.
+    pub fn num_antennas(&self) -> usize {
+        self.inputs
+            .iter()
+            .map(|mapping| mapping.antenna + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The antenna/pol that wire-order input `input` carries.
+    pub fn antenna_pol_for_input(&self, input: usize) -> Option<(usize, InputPol)> {
+        self.inputs
+            .get(input)
+            .map(|mapping| (mapping.antenna, mapping.pol))
+    }
+
+    /// The wire-order input index carrying `antenna`'s `pol`, if any.
+    pub fn input_for_antenna_pol(&self, antenna: usize, pol: InputPol) -> Option<usize> {
+        self.inputs
+            .iter()
+            .position(|mapping| mapping.antenna == antenna && mapping.pol == pol)
+    }
+}
+
+/// Given a pair of raw correlator input indices (in `input_map`'s M&C wire
+/// order), return the mwalib baseline number (in the clean antenna
+/// numbering) they form.
+///
+/// # Returns
+///
+/// * `None` if either input index is out of range for `input_map`.
+///
+pub fn baseline_from_inputs(input1: usize, input2: usize, input_map: &InputOrder) -> Option<usize> {
+    let (ant1, _) = input_map.antenna_pol_for_input(input1)?;
+    let (ant2, _) = input_map.antenna_pol_for_input(input2)?;
+
+    let (lo, hi) = if ant1 <= ant2 { (ant1, ant2) } else { (ant2, ant1) };
+
+    get_baseline_from_antennas(BaselineOrder::CrossAndAuto, lo, hi, input_map.num_antennas())
+}
+
+/// Translate a raw correlator-product index (a baseline index over raw wire
+/// inputs rather than clean antenna indices) into the `(ant1, ant2)` pair
+/// this module's triangle expects.
+pub fn antennas_from_correlator_product(
+    order: BaselineOrder,
+    product_index: usize,
+    input_map: &InputOrder,
+) -> Option<(usize, usize)> {
+    let num_inputs = input_map.inputs.len();
+    let (input1, input2) = get_antennas_from_baseline(order, product_index, num_inputs)?;
+
+    let (ant1, _) = input_map.antenna_pol_for_input(input1)?;
+    let (ant2, _) = input_map.antenna_pol_for_input(input2)?;
+
+    Some(if ant1 <= ant2 { (ant1, ant2) } else { (ant2, ant1) })
+}
+
+/// The inverse of [`antennas_from_correlator_product`]: given an antenna
+/// pair and the polarisation of interest, return the raw correlator-product
+/// index.
+pub fn correlator_product_from_antennas(
+    order: BaselineOrder,
+    ant1: usize,
+    ant2: usize,
+    pol: InputPol,
+    input_map: &InputOrder,
+) -> Option<usize> {
+    let input1 = input_map.input_for_antenna_pol(ant1, pol)?;
+    let input2 = input_map.input_for_antenna_pol(ant2, pol)?;
+
+    let (lo, hi) = if input1 <= input2 {
+        (input1, input2)
+    } else {
+        (input2, input1)
+    };
+
+    get_baseline_from_antennas(order, lo, hi, input_map.inputs.len())
+}
+
+/// Convert a UNIX time (in milliseconds) into the equivalent GPS time (in milliseconds),
+/// given the scheduled start of the observation expressed in both time standards.
+///
+/// This is a thin, millisecond-precision wrapper around
+/// [`convert_unixtime_to_gpstime_ns`] and is kept for callers that don't need
+/// nanosecond precision.
+///
+/// # Arguments
+///
+/// * `unix_time_ms` - The UNIX time to convert, in milliseconds.
+///
+/// * `scheduled_starttime_gps_ms` - Scheduled start time of the observation (GPS time), in milliseconds.
+///
+/// * `scheduled_starttime_unix_ms` - Scheduled start time of the observation (UNIX time), in milliseconds.
+///
+///
+/// # Returns
+///
+/// * The equivalent GPS time, in milliseconds.
+///
+pub fn convert_unixtime_to_gpstime(
+    unix_time_ms: u64,
+    scheduled_starttime_gps_ms: u64,
+    scheduled_starttime_unix_ms: u64,
+) -> u64 {
+    (convert_unixtime_to_gpstime_ns(
+        unix_time_ms * 1_000_000,
+        scheduled_starttime_gps_ms * 1_000_000,
+        scheduled_starttime_unix_ms * 1_000_000,
+    )) / 1_000_000
+}
+
+/// Convert a GPS time (in milliseconds) into the equivalent UNIX time (in milliseconds),
+/// given the scheduled start of the observation expressed in both time standards.
+///
+/// This is a thin, millisecond-precision wrapper around
+/// [`convert_gpstime_to_unixtime_ns`] and is kept for callers that don't need
+/// nanosecond precision.
+///
+/// # Arguments
+///
+/// * `gps_time_ms` - The GPS time to convert, in milliseconds.
+///
+/// * `scheduled_starttime_gps_ms` - Scheduled start time of the observation (GPS time), in milliseconds.
+///
+/// * `scheduled_starttime_unix_ms` - Scheduled start time of the observation (UNIX time), in milliseconds.
+///
+///
+/// # Returns
+///
+/// * The equivalent UNIX time, in milliseconds.
+///
+pub fn convert_gpstime_to_unixtime(
+    gps_time_ms: u64,
+    scheduled_starttime_gps_ms: u64,
+    scheduled_starttime_unix_ms: u64,
+) -> u64 {
+    (convert_gpstime_to_unixtime_ns(
+        gps_time_ms * 1_000_000,
+        scheduled_starttime_gps_ms * 1_000_000,
+        scheduled_starttime_unix_ms * 1_000_000,
+    )) / 1_000_000
+}
+
+/// Convert a UNIX time (in nanoseconds) into the equivalent GPS time (in nanoseconds),
+/// given the scheduled start of the observation expressed in both time standards.
+///
+/// The conversion is a pure integer offset (`unix - scheduled_unix + scheduled_gps`); no
+/// float round-trip is involved, so it never loses sub-millisecond precision. Leap
+/// seconds are assumed to already be baked into `scheduled_starttime_gps_ns`, as they
+/// are when it is derived from the metafits GPSTIME key.
+///
+/// # Arguments
+///
+/// * `unix_time_ns` - The UNIX time to convert, in nanoseconds.
+///
+/// * `scheduled_starttime_gps_ns` - Scheduled start time of the observation (GPS time), in nanoseconds.
+///
+/// * `scheduled_starttime_unix_ns` - Scheduled start time of the observation (UNIX time), in nanoseconds.
+///
+///
+/// # Returns
+///
+/// * The equivalent GPS time, in nanoseconds.
+///
+pub fn convert_unixtime_to_gpstime_ns(
+    unix_time_ns: u64,
+    scheduled_starttime_gps_ns: u64,
+    scheduled_starttime_unix_ns: u64,
+) -> u64 {
+    (unix_time_ns as i128 - scheduled_starttime_unix_ns as i128
+        + scheduled_starttime_gps_ns as i128) as u64
+}
+
+/// Semi-major axis of the WGS84 reference ellipsoid, in metres.
+const WGS84_SEMI_MAJOR_AXIS_M: f64 = 6_378_137.0;
+
+/// First eccentricity squared of the WGS84 reference ellipsoid.
+const WGS84_ECCENTRICITY_SQUARED: f64 = 0.006_694_379_990_13;
+
+/// Convert a geodetic position (latitude, longitude, height above the WGS84
+/// ellipsoid) into geocentric (ECEF) X/Y/Z, in metres.
+///
+/// # Arguments
+///
+/// * `latitude_rad` - Geodetic latitude, in radians.
+///
+/// * `longitude_rad` - Geodetic longitude, in radians.
+///
+/// * `height_m` - Height above the WGS84 ellipsoid, in metres.
+///
+///
+/// # Returns
+///
+/// * A tuple of (X, Y, Z) geocentric coordinates, in metres.
+///
+pub fn geodetic_to_geocentric_xyz(
+    latitude_rad: f64,
+    longitude_rad: f64,
+    height_m: f64,
+) -> (f64, f64, f64) {
+    let sin_lat = latitude_rad.sin();
+    let cos_lat = latitude_rad.cos();
+    let sin_lon = longitude_rad.sin();
+    let cos_lon = longitude_rad.cos();
+
+    // Radius of curvature in the prime vertical.
+    let n = WGS84_SEMI_MAJOR_AXIS_M / (1.0 - WGS84_ECCENTRICITY_SQUARED * sin_lat * sin_lat).sqrt();
+
+    let x = (n + height_m) * cos_lat * cos_lon;
+    let y = (n + height_m) * cos_lat * sin_lon;
+    let z = (n * (1.0 - WGS84_ECCENTRICITY_SQUARED) + height_m) * sin_lat;
+
+    (x, y, z)
+}
+
+/// Convert a tile's East-North-Height offset from the array centre into local
+/// X/Y/Z (metres), using the standard ENH to local-XYZ rotation by the array
+/// latitude.
+///
+/// # Arguments
+///
+/// * `north_m` - Tile offset to the North of the array centre, in metres.
+///
+/// * `east_m` - Tile offset to the East of the array centre, in metres.
+///
+/// * `height_m` - Tile offset above the array centre, in metres.
+///
+/// * `array_latitude_rad` - Geodetic latitude of the array centre, in radians.
+///
+///
+/// # Returns
+///
+/// * A tuple of local (X, Y, Z) coordinates relative to the array centre, in metres.
+///
+pub fn enh_to_local_xyz(
+    north_m: f64,
+    east_m: f64,
+    height_m: f64,
+    array_latitude_rad: f64,
+) -> (f64, f64, f64) {
+    let sin_lat = array_latitude_rad.sin();
+    let cos_lat = array_latitude_rad.cos();
+
+    let x = -sin_lat * north_m + cos_lat * height_m;
+    let y = east_m;
+    let z = cos_lat * north_m + sin_lat * height_m;
+
+    (x, y, z)
+}
+
+/// Rotate a baseline vector from local XYZ (the East-North-Height-derived
+/// frame produced by [`enh_to_local_xyz`]) into UVW, for a given hour angle
+/// and declination of the phase centre.
+///
+/// # Arguments
+///
+/// * `delta_x_m` - Baseline vector X component (antenna2 - antenna1), in metres.
+///
+/// * `delta_y_m` - Baseline vector Y component (antenna2 - antenna1), in metres.
+///
+/// * `delta_z_m` - Baseline vector Z component (antenna2 - antenna1), in metres.
+///
+/// * `hour_angle_rad` - Hour angle of the phase centre, in radians (LST - RA).
+///
+/// * `dec_rad` - Declination of the phase centre, in radians.
+///
+///
+/// # Returns
+///
+/// * A tuple of (u, v, w) coordinates, in metres.
+///
+pub fn xyz_to_uvw(
+    delta_x_m: f64,
+    delta_y_m: f64,
+    delta_z_m: f64,
+    hour_angle_rad: f64,
+    dec_rad: f64,
+) -> (f64, f64, f64) {
+    let (sin_ha, cos_ha) = hour_angle_rad.sin_cos();
+    let (sin_dec, cos_dec) = dec_rad.sin_cos();
+
+    let u = sin_ha * delta_x_m + cos_ha * delta_y_m;
+    let v = -sin_dec * cos_ha * delta_x_m + sin_dec * sin_ha * delta_y_m + cos_dec * delta_z_m;
+    let w = cos_dec * cos_ha * delta_x_m - cos_dec * sin_ha * delta_y_m + sin_dec * delta_z_m;
+
+    (u, v, w)
+}
+
+/// Convert a GPS time (in nanoseconds) into the equivalent UNIX time (in nanoseconds),
+/// given the scheduled start of the observation expressed in both time standards.
+///
+/// See [`convert_unixtime_to_gpstime_ns`] for the integer-arithmetic rationale.
+///
+/// # Arguments
+///
+/// * `gps_time_ns` - The GPS time to convert, in nanoseconds.
+///
+/// * `scheduled_starttime_gps_ns` - Scheduled start time of the observation (GPS time), in nanoseconds.
+///
+/// * `scheduled_starttime_unix_ns` - Scheduled start time of the observation (UNIX time), in nanoseconds.
+///
+///
+/// # Returns
+///
+/// * The equivalent UNIX time, in nanoseconds.
+///
+pub fn convert_gpstime_to_unixtime_ns(
+    gps_time_ns: u64,
+    scheduled_starttime_gps_ns: u64,
+    scheduled_starttime_unix_ns: u64,
+) -> u64 {
+    (gps_time_ns as i128 - scheduled_starttime_gps_ns as i128
+        + scheduled_starttime_unix_ns as i128) as u64
+}
+
+/// Modified Julian Date of the J2000.0 epoch.
+const MJD_J2000: f64 = 51544.5;
+
+/// Arcseconds to radians.
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// A 3x3 rotation matrix, row-major.
+pub type Matrix3 = [[f64; 3]; 3];
+
+/// Multiply a [`Matrix3`] by a 3-vector.
+///
+/// # Arguments
+///
+/// * `matrix` - The rotation matrix to apply.
+///
+/// * `vec` - The (x, y, z) vector to rotate.
+///
+///
+/// # Returns
+///
+/// * The rotated (x, y, z) vector.
+///
+pub fn rotate_vec3(matrix: Matrix3, vec: (f64, f64, f64)) -> (f64, f64, f64) {
+    let (x, y, z) = vec;
+    (
+        matrix[0][0] * x + matrix[0][1] * y + matrix[0][2] * z,
+        matrix[1][0] * x + matrix[1][1] * y + matrix[1][2] * z,
+        matrix[2][0] * x + matrix[2][1] * y + matrix[2][2] * z,
+    )
+}
+
+/// Multiply two [`Matrix3`]s (`a` * `b`).
+fn matmul3(a: Matrix3, b: Matrix3) -> Matrix3 {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    out
+}
+
+/// A rotation of `angle_rad` about the Z axis.
+fn rotation_z(angle_rad: f64) -> Matrix3 {
+    let (sin_a, cos_a) = angle_rad.sin_cos();
+    [
+        [cos_a, sin_a, 0.0],
+        [-sin_a, cos_a, 0.0],
+        [0.0, 0.0, 1.0],
+    ]
+}
+
+/// A rotation of `angle_rad` about the Y axis.
+fn rotation_y(angle_rad: f64) -> Matrix3 {
+    let (sin_a, cos_a) = angle_rad.sin_cos();
+    [
+        [cos_a, 0.0, -sin_a],
+        [0.0, 1.0, 0.0],
+        [sin_a, 0.0, cos_a],
+    ]
+}
+
+/// Build the classical IAU 1976 (Lieske) precession rotation matrix from the
+/// J2000.0 mean equator/equinox to the mean equator/equinox of the date given
+/// by `mjd`.
+///
+/// This deliberately implements only precession, not nutation - the
+/// dominant, slowly-accumulating arcminute-per-decade term that matters for
+/// de-precessing MWA phase centres and tile positions. It avoids pulling in
+/// an external IAU SOFA/ERFA binding for the sub-arcsecond nutation terms,
+/// which are well below mwalib's position accuracy budget.
+///
+/// # Arguments
+///
+/// * `mjd` - Modified Julian Date of the epoch to precess to.
+///
+///
+/// # Returns
+///
+/// * The 3x3 rotation matrix. Applying it (via [`rotate_vec3`]) to a J2000
+///   mean-equatorial unit vector yields the mean-equatorial unit vector of date.
+///
+pub fn precession_matrix(mjd: f64) -> Matrix3 {
+    let t = (mjd - MJD_J2000) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let zeta_rad = (2306.2181 * t + 0.30188 * t2 + 0.017998 * t3) * ARCSEC_TO_RAD;
+    let z_rad = (2306.2181 * t + 1.09468 * t2 + 0.018203 * t3) * ARCSEC_TO_RAD;
+    let theta_rad = (2004.3109 * t - 0.42665 * t2 - 0.041833 * t3) * ARCSEC_TO_RAD;
+
+    matmul3(
+        matmul3(rotation_z(-z_rad), rotation_y(theta_rad)),
+        rotation_z(-zeta_rad),
+    )
+}
@@ -0,0 +1,117 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/*!
+PFB (poly-phase filterbank) fine-channel passband gain correction.
+
+The MWA's PFB imposes a "scalloped" gain shape across the fine channels
+within each coarse channel; `mwalibContext::correct_passband` removes it using
+one of the gain curves below, selected by correlator generation and fine
+channel resolution.
+*/
+use crate::context::CorrelatorVersion;
+
+/// Relative PFB passband gain across a coarse channel, for the legacy
+/// correlator's 10 kHz fine channel resolution, normalised to ~1.0 across the
+/// channel centre and rolling off toward the coarse channel edges.
+pub const LEGACY_10KHZ_PFB_GAINS: [f64; 32] = [
+    0.003255, 0.634705, 0.714703, 0.769379, 0.812277, 0.847818, 0.877999, 0.903883, 0.926095,
+    0.945029, 0.960941, 0.974007, 0.984346, 0.992039, 0.99714, 0.999683, 0.999683, 0.99714,
+    0.992039, 0.984346, 0.974007, 0.960941, 0.945029, 0.926095, 0.903883, 0.877999, 0.847818,
+    0.812277, 0.769379, 0.714703, 0.634705, 0.003255,
+];
+
+/// Relative PFB passband gain across a coarse channel, for MWAX's finer
+/// (sub-kHz) fine channel resolution, normalised the same way as
+/// [`LEGACY_10KHZ_PFB_GAINS`].
+pub const MWAX_FINE_PFB_GAINS: [f64; 64] = [
+    0.003255, 0.565994, 0.633026, 0.677784, 0.712677, 0.741815, 0.767094, 0.789548, 0.809801,
+    0.828262, 0.845211, 0.860849, 0.875322, 0.888743, 0.901197, 0.912752, 0.923463, 0.933371,
+    0.942514, 0.950918, 0.958609, 0.965606, 0.971925, 0.97758, 0.982583, 0.986942, 0.990665,
+    0.993759, 0.996228, 0.998077, 0.999308, 0.999923, 0.999923, 0.999308, 0.998077, 0.996228,
+    0.993759, 0.990665, 0.986942, 0.982583, 0.97758, 0.971925, 0.965606, 0.958609, 0.950918,
+    0.942514, 0.933371, 0.923463, 0.912752, 0.901197, 0.888743, 0.875322, 0.860849, 0.845211,
+    0.828262, 0.809801, 0.789548, 0.767094, 0.741815, 0.712677, 0.677784, 0.633026, 0.565994,
+    0.003255,
+];
+
+/// Which shipped gain curve `mwalibContext::correct_passband` selected for a
+/// given correlator version/fine channel resolution combination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassbandTable {
+    /// `LEGACY_10KHZ_PFB_GAINS`, used for the legacy correlator's 10 kHz fine channels.
+    Legacy10kHz,
+    /// `MWAX_FINE_PFB_GAINS`, used for MWAX's sub-kHz fine channels.
+    MwaxFine,
+}
+
+impl PassbandTable {
+    /// The gain curve backing this table, at its own native resolution.
+    pub fn gains(&self) -> &'static [f64] {
+        match self {
+            PassbandTable::Legacy10kHz => &LEGACY_10KHZ_PFB_GAINS,
+            PassbandTable::MwaxFine => &MWAX_FINE_PFB_GAINS,
+        }
+    }
+}
+
+/// Select the passband table appropriate for `corr_version`/
+/// `fine_channel_width_hz`. MWAX observations coarser than 1 kHz fine
+/// channels are treated as using the same passband shape as the legacy
+/// correlator's 10 kHz channels.
+pub fn select_table(corr_version: CorrelatorVersion, fine_channel_width_hz: u32) -> PassbandTable {
+    match corr_version {
+        CorrelatorVersion::OldLegacy | CorrelatorVersion::Legacy => PassbandTable::Legacy10kHz,
+        CorrelatorVersion::V2 => {
+            if fine_channel_width_hz < 1000 {
+                PassbandTable::MwaxFine
+            } else {
+                PassbandTable::Legacy10kHz
+            }
+        }
+    }
+}
+
+/// Resample `gains` (defined at its own native resolution) to `len` points.
+///
+/// When `len` is coarser than the native resolution (the usual case: the
+/// presets above are defined at a finer resolution than most observations'
+/// `fine_channel_width_hz`), each output bin is the mean of the native bins
+/// that fall within it, rather than a single interpolated sample, so the
+/// correction reflects the passband response integrated over the bin rather
+/// than a potentially unrepresentative point sample. When `len` is finer
+/// (upsampling), gaps between native samples are filled by linear
+/// interpolation/decimation instead.
+pub fn resample_gains(gains: &[f64], len: usize) -> Vec<f64> {
+    if len == 0 {
+        return Vec::new();
+    }
+    if len == gains.len() {
+        return gains.to_vec();
+    }
+    if len == 1 {
+        return vec![gains.iter().sum::<f64>() / gains.len() as f64];
+    }
+
+    if len < gains.len() {
+        return (0..len)
+            .map(|i| {
+                let start = i * gains.len() / len;
+                let end = ((i + 1) * gains.len() / len).max(start + 1);
+                let bin = &gains[start..end];
+                bin.iter().sum::<f64>() / bin.len() as f64
+            })
+            .collect();
+    }
+
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * (gains.len() - 1) as f64 / (len - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = (lo + 1).min(gains.len() - 1);
+            let frac = pos - lo as f64;
+            gains[lo] * (1. - frac) + gains[hi] * frac
+        })
+        .collect()
+}
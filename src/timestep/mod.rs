@@ -5,7 +5,10 @@
 /*!
 Structs and helper methods for timestep metadata
 */
+use crate::error::ErrorKind;
 use crate::misc;
+use crate::voltage_files::error::VoltageFileError;
+use hifitime::{Duration, Epoch};
 use std::collections::BTreeMap;
 use std::fmt;
 
@@ -14,35 +17,74 @@ mod test;
 
 /// This is a struct for our timesteps
 /// NOTE: correlator timesteps use unix time, voltage timesteps use gpstime, but we convert the two depending on what we are given
-#[derive(Clone)]
+///
+/// `#[repr(C)]` so that `ffi::mwalib_correlator_timesteps_borrow`/
+/// `mwalib_voltage_timesteps_borrow` can hand a pointer straight into a
+/// context's own `Vec<TimeStep>` across the FFI boundary, rather than
+/// allocating a parallel FFI-side copy per call.
+#[repr(C)]
+#[derive(Clone, Copy)]
 pub struct TimeStep {
-    /// UNIX time (in milliseconds to avoid floating point inaccuracy)
+    /// UNIX time (in milliseconds to avoid floating point inaccuracy). Derived from `unix_time_ns`.
     pub unix_time_ms: u64,
-    /// gps time (in milliseconds)
+    /// gps time (in milliseconds). Derived from `gps_time_ns`.
     pub gps_time_ms: u64,
+    /// UNIX time (in nanoseconds). `unix_time_ms` is derived from it using pure
+    /// integer arithmetic, so it never suffers the rounding a f64-seconds round-trip would.
+    pub unix_time_ns: u64,
+    /// gps time (in nanoseconds). `gps_time_ms` is derived from it using pure
+    /// integer arithmetic, so it never suffers the rounding a f64-seconds round-trip would.
+    pub gps_time_ns: u64,
 }
 
 impl TimeStep {
-    /// Creates a new, populated TimeStep struct
+    /// Creates a new, populated TimeStep struct from nanosecond-resolution times.
+    ///
+    /// The millisecond fields are derived from these nanosecond values (integer
+    /// division), so they remain available for backward compatibility without
+    /// introducing a second source of truth.
     ///
     /// # Arguments
     ///
-    /// * `unix_time_ms` - The UNIX time for this timestep, in milliseconds
+    /// * `unix_time_ns` - The UNIX time for this timestep, in nanoseconds
     ///
-    /// * `gps_time_ms` - The gps time for this timestep, in milliseconds
+    /// * `gps_time_ns` - The gps time for this timestep, in nanoseconds
     ///
     ///
     /// # Returns
     ///
     /// * A populated TimeStep struct
     ///
-    fn new(unix_time_ms: u64, gps_time_ms: u64) -> Self {
+    fn new(unix_time_ns: u64, gps_time_ns: u64) -> Self {
         TimeStep {
-            unix_time_ms,
-            gps_time_ms,
+            unix_time_ms: unix_time_ns / 1_000_000,
+            gps_time_ms: gps_time_ns / 1_000_000,
+            unix_time_ns,
+            gps_time_ns,
         }
     }
 
+    /// The UNIX time of this timestep as a [`hifitime::Epoch`].
+    ///
+    /// Callers wanting to do arithmetic on timestamps (e.g. adding half an
+    /// integration time) should use this rather than dividing `unix_time_ms` by
+    /// 1000, which round-trips through `f64` and can shift a timestamp onto the
+    /// wrong integration boundary for large obsids.
+    pub fn unix_epoch(&self) -> Epoch {
+        Epoch::from_unix_seconds(0.) + Duration::from_total_nanoseconds(self.unix_time_ns as i128)
+    }
+
+    /// The GPS time of this timestep as a [`hifitime::Epoch`].
+    pub fn gps_epoch(&self) -> Epoch {
+        Epoch::from_gpst_seconds(0.) + Duration::from_total_nanoseconds(self.gps_time_ns as i128)
+    }
+
+    /// The duration of this timestep expressed since the UNIX epoch, for callers
+    /// that want a [`hifitime::Duration`] rather than an [`Epoch`].
+    pub fn unix_duration(&self) -> Duration {
+        Duration::from_total_nanoseconds(self.unix_time_ns as i128)
+    }
+
     /// Creates a new, populated vector of TimeStep structs
     ///
     /// # Arguments
@@ -77,18 +119,76 @@ impl TimeStep {
         let mut timesteps: Vec<TimeStep> = vec![];
         for (unix_time_ms, m) in gpubox_time_map.iter() {
             if m.len() == num_gpubox_files {
-                let gps_time_ms = misc::convert_unixtime_to_gpstime(
-                    *unix_time_ms,
-                    scheduled_starttime_gps_ms,
-                    scheduled_starttime_unix_ms,
+                let unix_time_ns = *unix_time_ms * 1_000_000;
+                let gps_time_ns = misc::convert_unixtime_to_gpstime_ns(
+                    unix_time_ns,
+                    scheduled_starttime_gps_ms * 1_000_000,
+                    scheduled_starttime_unix_ms * 1_000_000,
                 );
-                timesteps.push(Self::new(*unix_time_ms, gps_time_ms));
+                timesteps.push(Self::new(unix_time_ns, gps_time_ns));
             }
         }
 
         Some(timesteps)
     }
 
+    /// Creates a new, populated vector of `TimeStepCoverage` structs covering *every*
+    /// timestep present in `gpubox_time_map`, not just those common to all gpubox files.
+    ///
+    /// Unlike [`TimeStep::populate_correlator_timesteps`], which silently drops any
+    /// timestep that is missing even a single coarse channel, this returns every
+    /// timestep along with which coarse-channel indices it has data for, so a caller
+    /// can choose how to handle partially-covered timesteps instead of having them
+    /// vanish.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpubox_time_map` - BTree structure containing the map of what gpubox
+    ///   files and timesteps we were supplied by the client.
+    ///
+    /// * `scheduled_starttime_gps_ms` - Scheduled start time of the observation based on GPSTIME in the metafits (obsid).
+    ///
+    /// * `scheduled_starttime_unix_ms` - Scheduled start time of the observation based on GOODTIME-QUACKTIM in the metafits.
+    ///
+    /// # Returns
+    ///
+    /// * A populated vector of `TimeStepCoverage` structs inside an Option, one per
+    ///   timestep found in `gpubox_time_map`. If the Option has a value of None, then
+    ///   `gpubox_time_map` is empty.
+    ///
+    pub(crate) fn populate_all_correlator_timesteps(
+        gpubox_time_map: &BTreeMap<u64, BTreeMap<usize, (usize, usize)>>,
+        scheduled_starttime_gps_ms: u64,
+        scheduled_starttime_unix_ms: u64,
+    ) -> Option<Vec<TimeStepCoverage>> {
+        if gpubox_time_map.is_empty() {
+            return None;
+        }
+
+        let num_gpubox_files: usize = gpubox_time_map.iter().map(|(_, m)| m.len()).max().unwrap();
+
+        let mut timesteps: Vec<TimeStepCoverage> = vec![];
+        for (unix_time_ms, m) in gpubox_time_map.iter() {
+            let unix_time_ns = *unix_time_ms * 1_000_000;
+            let gps_time_ns = misc::convert_unixtime_to_gpstime_ns(
+                unix_time_ns,
+                scheduled_starttime_gps_ms * 1_000_000,
+                scheduled_starttime_unix_ms * 1_000_000,
+            );
+            let mut coarse_channel_indices: Vec<usize> = m.keys().copied().collect();
+            coarse_channel_indices.sort_unstable();
+
+            timesteps.push(TimeStepCoverage {
+                timestep: Self::new(unix_time_ns, gps_time_ns),
+                coarse_channel_indices,
+                num_coarse_channels_present: m.len(),
+                is_common: m.len() == num_gpubox_files,
+            });
+        }
+
+        Some(timesteps)
+    }
+
     /// Creates a new, populated vector of TimeStep structs
     ///
     /// # Arguments
@@ -118,17 +218,164 @@ impl TimeStep {
         for gps_time in
             (start_gps_time_ms..end_gps_time_ms).step_by(voltage_file_interval_ms as usize)
         {
-            let unix_time_ms = misc::convert_gpstime_to_unixtime(
-                gps_time,
-                scheduled_starttime_gps_ms,
-                scheduled_starttime_unix_ms,
+            let gps_time_ns = gps_time * 1_000_000;
+            let unix_time_ns = misc::convert_gpstime_to_unixtime_ns(
+                gps_time_ns,
+                scheduled_starttime_gps_ms * 1_000_000,
+                scheduled_starttime_unix_ms * 1_000_000,
             );
 
-            timesteps.push(Self::new(unix_time_ms, gps_time));
+            timesteps.push(Self::new(unix_time_ns, gps_time_ns));
         }
 
         timesteps
     }
+
+    /// Time-average a populated vector of native `TimeStep`s down to one averaged
+    /// `TimeStep` per `factor` consecutive native timesteps.
+    ///
+    /// The averaged timestep's time is the midpoint of the group's span, measured
+    /// from the *start* of the group's first native timestep to the *end* of its
+    /// last native timestep (i.e. `last + native_width`), not the mean of the two
+    /// starts — a native `TimeStep`'s `unix_time_ns` marks when its integration
+    /// begins, so stopping at the last timestep's start would put the centroid
+    /// systematically half an integration early. `native_width` is derived from
+    /// the cadence between the first two elements of `timesteps`, which are
+    /// assumed to be equally spaced. This way downstream phase-centre and UVW
+    /// calculations land on the true integration centre.
+    ///
+    /// If `timesteps.len()` is not a multiple of `factor`, the final group is
+    /// averaged over whatever timesteps remain (a ragged group), rather than being
+    /// dropped. Callers that require the factor to divide evenly should check
+    /// `timesteps.len() % factor == 0` themselves before calling.
+    ///
+    /// # Arguments
+    ///
+    /// * `timesteps` - A populated, time-ordered slice of native `TimeStep`s.
+    ///
+    /// * `factor` - The number of consecutive native timesteps to average together.
+    ///
+    /// # Returns
+    ///
+    /// * A `Result` containing the reduced vector of averaged `TimeStep`s, or an
+    ///   `ErrorKind` if `factor` is zero.
+    ///
+    pub fn average(timesteps: &[TimeStep], factor: usize) -> Result<Vec<TimeStep>, ErrorKind> {
+        if factor == 0 {
+            return Err(ErrorKind::Custom(
+                "TimeStep::average: time-averaging factor must be at least 1".to_string(),
+            ));
+        }
+
+        // The native integration width, assumed constant across `timesteps`.
+        let native_width_ns = if timesteps.len() >= 2 {
+            timesteps[1].unix_time_ns.saturating_sub(timesteps[0].unix_time_ns)
+        } else {
+            0
+        };
+
+        Ok(timesteps
+            .chunks(factor)
+            .map(|group| {
+                let first = &group[0];
+                let last = &group[group.len() - 1];
+                let unix_time_ns = (first.unix_time_ns + last.unix_time_ns + native_width_ns) / 2;
+                let gps_time_ns = (first.gps_time_ns + last.gps_time_ns + native_width_ns) / 2;
+                Self::new(unix_time_ns, gps_time_ns)
+            })
+            .collect())
+    }
+
+    /// Scan a built, time-ordered vector of `TimeStep`s and return every place where
+    /// the gap between consecutive timesteps is a multiple (greater than one) of
+    /// `expected_interval_ms`, i.e. somewhere in the middle of the observation is
+    /// missing one or more native timesteps.
+    ///
+    /// This lets a caller distinguish a genuinely contiguous observation from one
+    /// with dropouts before they start reading data, rather than assuming
+    /// `populate_voltage_timesteps`'s `step_by` cadence or the common keys found by
+    /// `populate_correlator_timesteps` are gapless.
+    ///
+    /// # Arguments
+    ///
+    /// * `timesteps` - A populated, time-ordered slice of native `TimeStep`s.
+    ///
+    /// * `expected_interval_ms` - The cadence (in ms) that should separate each
+    ///   consecutive pair of timesteps if there were no dropouts.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of `TimeGap`s, one per detected gap, in time order.
+    ///
+    pub fn find_gaps(timesteps: &[TimeStep], expected_interval_ms: u64) -> Vec<TimeGap> {
+        if expected_interval_ms == 0 {
+            return vec![];
+        }
+
+        timesteps
+            .windows(2)
+            .filter_map(|pair| {
+                let gap_ms = pair[1].gps_time_ms - pair[0].gps_time_ms;
+                let num_intervals = gap_ms / expected_interval_ms;
+                if num_intervals > 1 {
+                    Some(TimeGap {
+                        gap_start_gps_ms: pair[0].gps_time_ms,
+                        gap_end_gps_ms: pair[1].gps_time_ms,
+                        num_missing: num_intervals - 1,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Strict/contiguous-mode check: like [`TimeStep::find_gaps`], but returns an
+    /// `Err` on the first detected gap instead of structured data, for callers (e.g.
+    /// voltage file readers) that want to fail fast when an observation isn't
+    /// perfectly contiguous.
+    ///
+    /// # Arguments
+    ///
+    /// * `timesteps` - A populated, time-ordered slice of native `TimeStep`s.
+    ///
+    /// * `expected_interval_ms` - The cadence (in ms) that should separate each
+    ///   consecutive pair of timesteps if there were no dropouts.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if `timesteps` is perfectly contiguous, or a `VoltageFileError::GpsTimeMissing`
+    ///   describing the expected vs. actual count otherwise.
+    ///
+    pub fn check_contiguous(
+        timesteps: &[TimeStep],
+        expected_interval_ms: u64,
+    ) -> Result<(), VoltageFileError> {
+        let gaps = Self::find_gaps(timesteps, expected_interval_ms);
+        match gaps.first() {
+            Some(gap) => {
+                let expected = (gap.gap_end_gps_ms - gap.gap_start_gps_ms) / expected_interval_ms;
+                Err(VoltageFileError::GpsTimeMissing {
+                    expected,
+                    got: expected - gap.num_missing,
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// A single detected gap in an otherwise time-ordered vector of timesteps: a place
+/// where the interval between two consecutive timesteps is a whole multiple
+/// (greater than one) of the expected cadence.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeGap {
+    /// GPS time (in ms) of the timestep immediately before the gap.
+    pub gap_start_gps_ms: u64,
+    /// GPS time (in ms) of the timestep immediately after the gap.
+    pub gap_end_gps_ms: u64,
+    /// The number of native timesteps missing within the gap.
+    pub num_missing: u64,
 }
 
 /// Implements fmt::Debug for TimeStep struct
@@ -153,3 +400,34 @@ impl fmt::Debug for TimeStep {
         )
     }
 }
+
+/// A single timestep from `gpubox_time_map`, annotated with how many (and which)
+/// gpubox coarse channels actually have data for it.
+///
+/// Returned by [`TimeStep::populate_all_correlator_timesteps`], which - unlike
+/// [`TimeStep::populate_correlator_timesteps`] - reports every timestep present in
+/// at least one gpubox file, rather than only those common to all of them.
+#[derive(Clone)]
+pub struct TimeStepCoverage {
+    /// The timestep itself.
+    pub timestep: TimeStep,
+    /// The coarse-channel indices (keys into `gpubox_time_map`'s inner map) that
+    /// have data for this timestep.
+    pub coarse_channel_indices: Vec<usize>,
+    /// The number of coarse channels present for this timestep.
+    pub num_coarse_channels_present: usize,
+    /// `true` if this timestep has data for every coarse channel (i.e. it would
+    /// also appear in `TimeStep::populate_correlator_timesteps`'s output).
+    pub is_common: bool,
+}
+
+/// Implements fmt::Debug for TimeStepCoverage struct
+impl fmt::Debug for TimeStepCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} (coarse chans present={}, common={})",
+            self.timestep, self.num_coarse_channels_present, self.is_common,
+        )
+    }
+}